@@ -1,207 +1,41 @@
-use aoc_2023::solutions::day01::{solution_day_01_01, solution_day_01_02};
-use aoc_2023::solutions::day02::{solution_day_02_01, solution_day_02_02};
-use aoc_2023::solutions::day03::{solution_day_03_01, solution_day_03_02};
-use aoc_2023::solutions::day04::{solution_day_04_01, solution_day_04_02};
-use aoc_2023::solutions::day05::{solution_day_05_01, solution_day_05_02};
-use aoc_2023::solutions::day06::{solution_day_06_01, solution_day_06_02};
-use aoc_2023::solutions::day07::{solution_day_07_01, solution_day_07_02};
-use aoc_2023::solutions::day08::{solution_day_08_01, solution_day_08_02};
-use aoc_2023::solutions::day09::{solution_day_09_01, solution_day_09_02};
-use aoc_2023::solutions::day10::{solution_day_10_01, solution_day_10_02};
-use aoc_2023::solutions::day11::solution_day_11;
-use aoc_2023::solutions::day12::{solution_day_12_01, solution_day_12_02};
-use aoc_2023::solutions::day13::{solution_day_13_01, solution_day_13_02};
-use aoc_2023::solutions::day14::{solution_day_14_01, solution_day_14_02};
-use aoc_2023::solutions::day15::{solution_day_15_01, solution_day_15_02};
-use aoc_2023::solutions::day16::{solution_day_16_01, solution_day_16_02};
-use aoc_2023::solutions::day17::{solution_day_17_01, solution_day_17_02};
-use aoc_2023::solutions::day18::{solution_day_18_01, solution_day_18_02};
-use aoc_2023::solutions::day19::{solution_day_19_01, solution_day_19_02};
-use aoc_2023::solutions::day20::{solution_day_20_01, solution_day_20_02};
+use std::env;
+
+use aoc_2023::solutions::{find_event, read_input, DEFAULT_EVENT, DEFAULT_ROOT};
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
 
+// Inputs are read once, so only parsing and solving are timed, each under its own id.
+// `AOC_EVENT` and `AOC_ROOT` pick another event, like `--event` and `--root` do for the runner.
 fn all_solutions(c: &mut Criterion) {
+    let name = env::var("AOC_EVENT").unwrap_or_else(|_| DEFAULT_EVENT.to_string());
+    let root = env::var("AOC_ROOT").unwrap_or_else(|_| DEFAULT_ROOT.to_string());
+    let event = find_event(&name).unwrap_or_else(|| panic!("Unknown event \"{}\".", name));
     let mut g = c.benchmark_group("Solutions");
 
-    // day 01
-    let file_path: String = String::from("src/inputs/day01.txt");
-    g.bench_function("Day 01/Part 01", |b| {
-        b.iter(|| solution_day_01_01(black_box(file_path.clone())))
-    });
-    g.bench_function("Day 01/Part 02", |b| {
-        b.iter(|| solution_day_01_02(black_box(file_path.clone())))
-    });
-
-    // Day 02
-    let file_path: String = String::from("src/inputs/day02.txt");
-    g.bench_function("Day 02/Part 01", |b| {
-        b.iter(|| solution_day_02_01(black_box(file_path.clone())))
-    });
-    g.bench_function("Day 02/Part 02", |b| {
-        b.iter(|| solution_day_02_02(black_box(file_path.clone())))
-    });
-
-    // Day 03
-    let file_path: String = String::from("src/inputs/day03.txt");
-    g.bench_function("Day 03/Part 01", |b| {
-        b.iter(|| solution_day_03_01(black_box(file_path.clone())))
-    });
-    g.bench_function("Day 03/Part 02", |b| {
-        b.iter(|| solution_day_03_02(black_box(file_path.clone())))
-    });
-
-    // Day 04
-    let file_path: String = String::from("src/inputs/day04.txt");
-    g.bench_function("Day 04/Part 01", |b| {
-        b.iter(|| solution_day_04_01(black_box(file_path.clone())))
-    });
-    g.bench_function("Day 04/Part 02", |b| {
-        b.iter(|| solution_day_04_02(black_box(file_path.clone())))
-    });
-
-    // Day 05
-    let file_path: String = String::from("src/inputs/day05.txt");
-    g.bench_function("Day 05/Part 01", |b| {
-        b.iter(|| solution_day_05_01(black_box(file_path.clone())))
-    });
-    g.bench_function("Day 05/Part 02", |b| {
-        b.iter(|| solution_day_05_02(black_box(file_path.clone())))
-    });
-
-    // Day 06
-    let file_path: String = String::from("src/inputs/day06.txt");
-    g.bench_function("Day 06/Part 01", |b| {
-        b.iter(|| solution_day_06_01(black_box(file_path.clone())))
-    });
-    g.bench_function("Day 06/Part 02", |b| {
-        b.iter(|| solution_day_06_02(black_box(file_path.clone())))
-    });
-
-    // Day 07
-    let file_path: String = String::from("src/inputs/day07.txt");
-    g.bench_function("Day 07/Part 01", |b| {
-        b.iter(|| solution_day_07_01(black_box(file_path.clone())))
-    });
-    g.bench_function("Day 07/Part 02", |b| {
-        b.iter(|| solution_day_07_02(black_box(file_path.clone())))
-    });
-
-    // Day 08
-    let file_path: String = String::from("src/inputs/day08.txt");
-    g.bench_function("Day 08/Part 01", |b| {
-        b.iter(|| solution_day_08_01(black_box(file_path.clone())))
-    });
-    g.bench_function("Day 08/Part 02", |b| {
-        b.iter(|| solution_day_08_02(black_box(file_path.clone())))
-    });
-
-    // Day 09
-    let file_path: String = String::from("src/inputs/day09.txt");
-    g.bench_function("Day 09/Part 01", |b| {
-        b.iter(|| solution_day_09_01(black_box(file_path.clone())))
-    });
-    g.bench_function("Day 09/Part 02", |b| {
-        b.iter(|| solution_day_09_02(black_box(file_path.clone())))
-    });
-
-    // Day 10
-    let file_path: String = String::from("src/inputs/day10.txt");
-    g.bench_function("Day 10/Part 01", |b| {
-        b.iter(|| solution_day_10_01(black_box(file_path.clone())))
-    });
-    g.bench_function("Day 10/Part 02", |b| {
-        b.iter(|| solution_day_10_02(black_box(file_path.clone())))
-    });
-
-    // Day 11
-    let file_path: String = String::from("src/inputs/day11.txt");
-    g.bench_function("Day 11/Part 01", |b| {
-        b.iter(|| solution_day_11(black_box(file_path.clone()), 2))
-    });
-    g.bench_function("Day 11/Part 02", |b| {
-        b.iter(|| solution_day_11(black_box(file_path.clone()), 1000000))
-    });
-
-    // Day 12
-    let file_path: String = String::from("src/inputs/day12.txt");
-    g.bench_function("Day 12/Part 01", |b| {
-        b.iter(|| solution_day_12_01(black_box(file_path.clone())))
-    });
-    g.bench_function("Day 12/Part 02", |b| {
-        b.iter(|| solution_day_12_02(black_box(file_path.clone())))
-    });
-
-    // Day 13
-    let file_path: String = String::from("src/inputs/day13.txt");
-    g.bench_function("Day 13/Part 01", |b| {
-        b.iter(|| solution_day_13_01(black_box(file_path.clone())))
-    });
-    g.bench_function("Day 13/Part 02", |b| {
-        b.iter(|| solution_day_13_02(black_box(file_path.clone())))
-    });
-
-    // Day 14
-    let file_path: String = String::from("src/inputs/day14.txt");
-    g.bench_function("Day 14/Part 01", |b| {
-        b.iter(|| solution_day_14_01(black_box(file_path.clone())))
-    });
-    g.bench_function("Day 14/Part 02", |b| {
-        b.iter(|| solution_day_14_02(black_box(file_path.clone())))
-    });
-
-    // Day 15
-    let file_path: String = String::from("src/inputs/day15.txt");
-    g.bench_function("Day 15/Part 01", |b| {
-        b.iter(|| solution_day_15_01(black_box(file_path.clone())))
-    });
-    g.bench_function("Day 15/Part 02", |b| {
-        b.iter(|| solution_day_15_02(black_box(file_path.clone())))
-    });
-
-    // Day 16
-    let file_path: String = String::from("src/inputs/day16.txt");
-    g.bench_function("Day 16/Part 01", |b| {
-        b.iter(|| solution_day_16_01(black_box(file_path.clone())))
-    });
-    g.bench_function("Day 16/Part 02", |b| {
-        b.iter(|| solution_day_16_02(black_box(file_path.clone())))
-    });
-
-    // Day 17
-    let file_path: String = String::from("src/inputs/day17.txt");
-    g.bench_function("Day 17/Part 01", |b| {
-        b.iter(|| solution_day_17_01(black_box(file_path.clone())))
-    });
-    g.bench_function("Day 17/Part 02", |b| {
-        b.iter(|| solution_day_17_02(black_box(file_path.clone())))
-    });
-
-    // Day 18
-    let file_path: String = String::from("src/inputs/day18.txt");
-    g.bench_function("Day 18/Part 01", |b| {
-        b.iter(|| solution_day_18_01(black_box(file_path.clone())))
-    });
-    g.bench_function("Day 18/Part 02", |b| {
-        b.iter(|| solution_day_18_02(black_box(file_path.clone())))
-    });
-
-    // Day 19
-    let file_path: String = String::from("src/inputs/day19.txt");
-    g.bench_function("Day 19/Part 01", |b| {
-        b.iter(|| solution_day_19_01(black_box(file_path.clone())))
-    });
-    g.bench_function("Day 19/Part 02", |b| {
-        b.iter(|| solution_day_19_02(black_box(file_path.clone())))
-    });
-
-    // Day 20
-    let file_path: String = String::from("src/inputs/day20.txt");
-    g.bench_function("Day 20/Part 01", |b| {
-        b.iter(|| solution_day_20_01(black_box(file_path.clone())))
-    });
-    g.bench_function("Day 20/Part 02", |b| {
-        b.iter(|| solution_day_20_02(black_box(file_path.clone())))
-    });
+    for puzzle in event.puzzles {
+        // A day that was just scaffolded has no input yet, which shouldn't stop the others.
+        let loaded = read_input(event.input_path(&root, puzzle.day))
+            .and_then(|input| Ok((puzzle.parse(&input)?, input)));
+        let (parsed, input) = match loaded {
+            Ok(loaded) => loaded,
+            Err(e) => {
+                eprintln!(
+                    "Skipping day {:02} part {:02}: {}",
+                    puzzle.day, puzzle.part, e
+                );
+                continue;
+            }
+        };
+        // Both parts of a day share the parser, so it's only timed once.
+        if puzzle.part == 1 {
+            g.bench_function(format!("Day {:02}/Parse", puzzle.day), |b| {
+                b.iter(|| puzzle.parse(black_box(&input)))
+            });
+        }
+        g.bench_function(
+            format!("Day {:02}/Part {:02}", puzzle.day, puzzle.part),
+            |b| b.iter(|| puzzle.solve(black_box(parsed.as_ref()))),
+        );
+    }
 
     g.finish()
 }
@@ -0,0 +1,118 @@
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+// Only builds with `--features count-alloc` count anything, the others keep the system allocator.
+pub const ENABLED: bool = cfg!(feature = "count-alloc");
+
+#[cfg(feature = "count-alloc")]
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+static COUNT: AtomicUsize = AtomicUsize::new(0);
+static BYTES: AtomicUsize = AtomicUsize::new(0);
+static LIVE: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+// The system allocator, keeping count of what goes through it.
+pub struct CountingAllocator;
+
+fn record_alloc(size: usize) {
+    COUNT.fetch_add(1, Ordering::Relaxed);
+    BYTES.fetch_add(size, Ordering::Relaxed);
+    let live = LIVE.fetch_add(size, Ordering::Relaxed) + size;
+    PEAK.fetch_max(live, Ordering::Relaxed);
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        LIVE.fetch_sub(layout.size(), Ordering::Relaxed);
+    }
+
+    // A reallocation counts as a new allocation of the new size, like freeing and allocating again.
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            LIVE.fetch_sub(layout.size(), Ordering::Relaxed);
+            record_alloc(new_size);
+        }
+        new_ptr
+    }
+}
+
+// What a piece of code allocated, peak being the most it held at once on top of what was live before.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AllocStats {
+    pub count: usize,
+    pub bytes: usize,
+    pub peak: usize,
+}
+
+pub fn format_bytes(bytes: usize) -> String {
+    let (mut value, mut unit) = (bytes as f64, 0);
+    let units = ["B", "KB", "MB", "GB"];
+    while value > 1000.0 && unit + 1 < units.len() {
+        value /= 1000.0;
+        unit += 1;
+    }
+    match unit {
+        0 => format!("{}B", bytes),
+        _ => format!("{:.2}{}", value, units[unit]),
+    }
+}
+
+// The counters are shared by every thread, so measure one thing at a time.
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, AllocStats) {
+    let live = LIVE.load(Ordering::Relaxed);
+    PEAK.store(live, Ordering::Relaxed);
+    let (count, bytes) = (COUNT.load(Ordering::Relaxed), BYTES.load(Ordering::Relaxed));
+    let result = f();
+    let stats = AllocStats {
+        count: COUNT.load(Ordering::Relaxed) - count,
+        bytes: BYTES.load(Ordering::Relaxed) - bytes,
+        peak: PEAK.load(Ordering::Relaxed).saturating_sub(live),
+    };
+    (result, stats)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_alloc() {
+        let (_, stats) = measure(|| {
+            record_alloc(100);
+            record_alloc(50);
+            LIVE.fetch_sub(150, Ordering::Relaxed);
+        });
+        // Other tests may allocate at the same time, so these are lower bounds.
+        assert!(stats.count >= 2);
+        assert!(stats.bytes >= 150);
+        assert!(stats.peak >= 150);
+    }
+
+    #[test]
+    fn test_format_bytes() {
+        assert_eq!(format_bytes(512), "512B");
+        assert_eq!(format_bytes(3_400_000), "3.40MB");
+    }
+}
@@ -0,0 +1,239 @@
+use crate::error::{parse_lines, AocError, Result};
+use crate::solutions::{read_input, Event, Params};
+
+// Each event keeps its known answers next to its inputs.
+pub fn answers_path(event: &Event, root: &str) -> String {
+    format!("{}/answers.jsonl", event.inputs_dir(root))
+}
+
+// One known answer, for one part of a day run against one input file with the given parameters.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Expected {
+    pub day: u8,
+    pub part: u8,
+    pub input: String,
+    pub params: Params,
+    pub answer: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Status {
+    Pass,
+    Fail(String),
+    Missing,
+}
+
+impl Expected {
+    pub fn input_path(&self, event: &Event, root: &str) -> String {
+        format!("{}/{}", event.inputs_dir(root), self.input)
+    }
+
+    pub fn check(&self, event: &Event, root: &str) -> Status {
+        let puzzle = match event.find_puzzle(self.day, self.part) {
+            Some(puzzle) => puzzle,
+            None => return Status::Fail(String::from("not implemented")),
+        };
+        if let Some(name) = self
+            .params
+            .names()
+            .find(|n| !puzzle.params.iter().any(|p| p.name == *n))
+        {
+            return Status::Fail(format!("no parameter \"{}\"", name));
+        }
+        match puzzle.run_with(self.input_path(event, root), &self.params) {
+            Ok(answer) if answer.to_string() == self.answer => Status::Pass,
+            Ok(answer) => Status::Fail(format!("expected {}, got {}", self.answer, answer)),
+            Err(e) => Status::Fail(e.to_string()),
+        }
+    }
+}
+
+fn field<'a>(entry: &'a serde_json::Value, name: &str) -> Result<&'a serde_json::Value> {
+    entry
+        .get(name)
+        .ok_or_else(|| AocError::parse(1, 1, format!("missing \"{}\"", name)))
+}
+
+fn parse_number(entry: &serde_json::Value, name: &str) -> Result<u8> {
+    field(entry, name)?
+        .as_u64()
+        .and_then(|v| u8::try_from(v).ok())
+        .ok_or_else(|| AocError::parse(1, 1, format!("\"{}\" is not a small number", name)))
+}
+
+fn parse_string(entry: &serde_json::Value, name: &str) -> Result<String> {
+    field(entry, name)?
+        .as_str()
+        .map(|v| v.to_string())
+        .ok_or_else(|| AocError::parse(1, 1, format!("\"{}\" is not a string", name)))
+}
+
+// Parameters are optional, as an object of names and values.
+fn parse_params(entry: &serde_json::Value) -> Result<Params> {
+    let mut params = Params::default();
+    let Some(values) = entry.get("params") else {
+        return Ok(params);
+    };
+    let values = values
+        .as_object()
+        .ok_or_else(|| AocError::parse(1, 1, "\"params\" is not an object"))?;
+    for (name, value) in values {
+        let value = value
+            .as_u64()
+            .and_then(|v| usize::try_from(v).ok())
+            .ok_or_else(|| {
+                AocError::parse(1, 1, format!("parameter \"{}\" is not a number", name))
+            })?;
+        params.set(name, value);
+    }
+    Ok(params)
+}
+
+// The file holds one JSON object per line, so a bad entry can be reported by its line.
+pub fn parse_answers(input: &str) -> Result<Vec<Expected>> {
+    let entries = parse_lines(input, |l| {
+        if l.trim().is_empty() {
+            return Ok(None);
+        }
+        let entry: serde_json::Value =
+            serde_json::from_str(l).map_err(|e| AocError::parse(1, e.column(), e.to_string()))?;
+        Ok(Some(Expected {
+            day: parse_number(&entry, "day")?,
+            part: parse_number(&entry, "part")?,
+            input: parse_string(&entry, "input")?,
+            params: parse_params(&entry)?,
+            answer: parse_string(&entry, "answer")?,
+        }))
+    })?;
+    Ok(entries.into_iter().flatten().collect())
+}
+
+pub fn load_answers(file_path: &str) -> Result<Vec<Expected>> {
+    parse_answers(&read_input(file_path.to_string())?)
+}
+
+// Every known answer with its outcome, followed by the registered puzzles whose real input has
+// none. With a day, only that day is checked.
+pub fn check_answers(
+    event: &Event,
+    root: &str,
+    answers: &[Expected],
+    day: Option<u8>,
+) -> Vec<(Expected, Status)> {
+    let mut results: Vec<(Expected, Status)> = answers
+        .iter()
+        .filter(|e| day.is_none_or(|d| e.day == d))
+        .map(|e| (e.clone(), e.check(event, root)))
+        .collect();
+    for puzzle in event
+        .puzzles
+        .iter()
+        .filter(|p| day.is_none_or(|d| p.day == d))
+    {
+        let input = format!("day{:02}.txt", puzzle.day);
+        if !answers
+            .iter()
+            .any(|e| e.day == puzzle.day && e.part == puzzle.part && e.input == input)
+        {
+            let expected = Expected {
+                day: puzzle.day,
+                part: puzzle.part,
+                input,
+                params: Params::default(),
+                answer: String::new(),
+            };
+            results.push((expected, Status::Missing));
+        }
+    }
+    results
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solutions::{find_event, DEFAULT_EVENT, DEFAULT_ROOT, PUZZLES};
+
+    fn event() -> &'static Event {
+        find_event(DEFAULT_EVENT).unwrap()
+    }
+
+    #[test]
+    fn test_parse_answers() {
+        let answers = parse_answers(
+            "{\"day\": 2, \"part\": 1, \"input\": \"day02e.txt\", \"answer\": \"8\"}\n\n",
+        );
+        assert_eq!(
+            answers,
+            Ok(vec![Expected {
+                day: 2,
+                part: 1,
+                input: String::from("day02e.txt"),
+                params: Params::default(),
+                answer: String::from("8"),
+            }])
+        );
+        let answers = parse_answers("{\"day\": 2, \"part\": 1, \"input\": \"day02e.txt\"}");
+        assert_eq!(answers, Err(AocError::parse(1, 1, "missing \"answer\"")));
+        let answers = parse_answers(
+            "{\"day\": 11, \"part\": 2, \"input\": \"day11e.txt\", \"params\": {\"multiplier\": 10}, \"answer\": \"1030\"}",
+        )
+        .unwrap();
+        let mut params = Params::default();
+        params.set("multiplier", 10);
+        assert_eq!(answers[0].params, params);
+        assert_eq!(answers[0].check(event(), DEFAULT_ROOT), Status::Pass);
+        let answers = parse_answers(
+            "{\"day\": 11, \"part\": 2, \"input\": \"day11e.txt\", \"params\": {\"steps\": 10}, \"answer\": \"1030\"}",
+        )
+        .unwrap();
+        assert_eq!(
+            answers[0].check(event(), DEFAULT_ROOT),
+            Status::Fail(String::from("no parameter \"steps\""))
+        );
+    }
+
+    #[test]
+    fn test_check_answers() {
+        let answers = vec![
+            Expected {
+                day: 2,
+                part: 1,
+                input: String::from("day02e.txt"),
+                params: Params::default(),
+                answer: String::from("8"),
+            },
+            Expected {
+                day: 2,
+                part: 2,
+                input: String::from("day02e.txt"),
+                params: Params::default(),
+                answer: String::from("8"),
+            },
+        ];
+        // Both parts of day 2 are also missing their real answer.
+        let results = check_answers(event(), DEFAULT_ROOT, &answers, Some(2));
+        assert_eq!(results.len(), 4);
+        assert!(results.iter().all(|(e, _)| e.day == 2));
+        let results = check_answers(event(), DEFAULT_ROOT, &answers, None);
+        assert_eq!(results[0].1, Status::Pass);
+        assert_eq!(
+            results[1].1,
+            Status::Fail(String::from("expected 8, got 2286"))
+        );
+        assert_eq!(results.len(), 2 + PUZZLES.len());
+        assert_eq!(
+            answers_path(event(), DEFAULT_ROOT),
+            "src/inputs/answers.jsonl"
+        );
+        assert!(results[2..].iter().all(|(_, s)| s == &Status::Missing));
+    }
+
+    #[test]
+    #[ignore]
+    fn output_all_answers() {
+        let answers = load_answers(&answers_path(event(), DEFAULT_ROOT)).unwrap();
+        for (expected, status) in check_answers(event(), DEFAULT_ROOT, &answers, None) {
+            assert_eq!(status, Status::Pass, "{:?}", expected);
+        }
+    }
+}
@@ -0,0 +1,287 @@
+use std::{
+    collections::BTreeMap,
+    fs,
+    time::{Duration, Instant},
+};
+
+use crate::alloc::{self, AllocStats};
+use crate::error::{AocError, Result};
+use crate::solutions::{read_input, Params, Puzzle};
+
+pub const DEFAULT_SAMPLES: usize = 100;
+pub const DEFAULT_WARMUP: usize = 3;
+
+// Summary of the time taken by every run, in nanoseconds.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Stats {
+    pub min: f64,
+    pub median: f64,
+    pub mean: f64,
+    pub std_dev: f64,
+    pub samples: usize,
+}
+
+impl Stats {
+    pub fn from_samples(samples: &[Duration]) -> Self {
+        let mut values: Vec<f64> = samples.iter().map(|d| d.as_nanos() as f64).collect();
+        values.sort_by(|a, b| a.total_cmp(b));
+        let n = values.len();
+        if n == 0 {
+            return Stats {
+                min: 0.0,
+                median: 0.0,
+                mean: 0.0,
+                std_dev: 0.0,
+                samples: 0,
+            };
+        }
+        let median = if n.is_multiple_of(2) {
+            (values[n / 2 - 1] + values[n / 2]) / 2.0
+        } else {
+            values[n / 2]
+        };
+        let mean = values.iter().sum::<f64>() / n as f64;
+        let variance = if n > 1 {
+            values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / (n - 1) as f64
+        } else {
+            0.0
+        };
+        Stats {
+            min: values[0],
+            median,
+            mean,
+            std_dev: variance.sqrt(),
+            samples: n,
+        }
+    }
+
+    // The 95% confidence interval of the mean, assuming the times are normally distributed.
+    pub fn confidence_interval(&self) -> (f64, f64) {
+        let margin = 1.96 * self.std_dev / (self.samples.max(1) as f64).sqrt();
+        (self.mean - margin, self.mean + margin)
+    }
+}
+
+// Times whole runs, reading and parsing the input included.
+pub fn bench(
+    puzzle: &Puzzle,
+    input_path: &str,
+    params: &Params,
+    warmup: usize,
+    samples: usize,
+) -> Result<Stats> {
+    let run = || {
+        let input = read_input(input_path.to_string())?;
+        puzzle.solve_with(puzzle.parse(&input)?.as_ref(), params)
+    };
+    for _ in 0..warmup {
+        run()?;
+    }
+    let mut times = Vec::with_capacity(samples);
+    for _ in 0..samples.max(1) {
+        let start = Instant::now();
+        run()?;
+        times.push(start.elapsed());
+    }
+    Ok(Stats::from_samples(&times))
+}
+
+// Allocations of one run of parsing and solving, None unless the counting allocator is in use.
+pub fn measure_allocations(
+    puzzle: &Puzzle,
+    input_path: &str,
+    params: &Params,
+) -> Result<Option<AllocStats>> {
+    if !alloc::ENABLED {
+        return Ok(None);
+    }
+    let input = read_input(input_path.to_string())?;
+    let (result, stats) =
+        alloc::measure(|| puzzle.solve_with(puzzle.parse(&input)?.as_ref(), params));
+    result?;
+    Ok(Some(stats))
+}
+
+pub fn bench_id(puzzle: &Puzzle) -> String {
+    format!("Solutions/Day {:02}/Part {:02}", puzzle.day, puzzle.part)
+}
+
+fn estimate(value: f64) -> serde_json::Value {
+    serde_json::json!({ "estimate": value, "unit": "ns" })
+}
+
+// The same shape as `cargo criterion --message-format=json`, so `format_bench` reads either.
+// Allocations aren't part of criterion's output, `format_bench` shows them when they're there.
+pub fn criterion_record(
+    id: &str,
+    stats: &Stats,
+    allocations: Option<&AllocStats>,
+) -> serde_json::Value {
+    let (lower, upper) = stats.confidence_interval();
+    let mut record = serde_json::json!({
+        "reason": "benchmark-complete",
+        "id": id,
+        "unit": "ns",
+        "typical": {
+            "estimate": stats.mean,
+            "lower_bound": lower,
+            "upper_bound": upper,
+            "unit": "ns",
+        },
+        "mean": estimate(stats.mean),
+        "median": estimate(stats.median),
+        "min": estimate(stats.min),
+        "std_dev": estimate(stats.std_dev),
+    });
+    if let Some(a) = allocations {
+        record["allocations"] = serde_json::json!({
+            "count": a.count,
+            "bytes": a.bytes,
+            "peak": a.peak,
+        });
+    }
+    record
+}
+
+// Criterion reports times in any of these units.
+pub fn to_nanos(value: f64, unit: &str) -> Option<f64> {
+    let scale = match unit {
+        "ps" => 1e-3,
+        "ns" => 1.0,
+        "us" | "µs" | "μs" => 1e3,
+        "ms" => 1e6,
+        "s" => 1e9,
+        _ => return None,
+    };
+    Some(value * scale)
+}
+
+// The id and typical time in nanoseconds of a finished benchmark, other lines give None.
+pub fn parse_record(line: &str) -> Option<(String, f64)> {
+    let json: serde_json::Value = serde_json::from_str(line).ok()?;
+    if json.get("reason")? != "benchmark-complete" {
+        return None;
+    }
+    let typical = json.get("typical")?;
+    let nanos = to_nanos(
+        typical.get("estimate")?.as_f64()?,
+        typical.get("unit")?.as_str()?,
+    )?;
+    Some((json.get("id")?.as_str()?.to_string(), nanos))
+}
+
+// Every benchmark found in the JSON files of a directory, by id.
+pub fn load_results(dir: &str) -> Result<BTreeMap<String, f64>> {
+    let mut results = BTreeMap::new();
+    let entries = fs::read_dir(dir).map_err(|e| AocError::io(dir, e))?;
+    let mut paths: Vec<_> = entries.flatten().map(|e| e.path()).collect();
+    paths.sort();
+    for path in paths {
+        if path.extension().is_none_or(|e| e != "json") {
+            continue;
+        }
+        let name = path.to_string_lossy();
+        let content = fs::read_to_string(&path).map_err(|e| AocError::io(&name, e))?;
+        results.extend(content.lines().filter_map(parse_record));
+    }
+    Ok(results)
+}
+
+// One benchmark in two result sets, missing from either of them when it was added or removed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Change {
+    pub id: String,
+    pub baseline: Option<f64>,
+    pub current: Option<f64>,
+}
+
+impl Change {
+    pub fn percent(&self) -> Option<f64> {
+        match (self.baseline, self.current) {
+            (Some(b), Some(c)) if b > 0.0 => Some((c / b - 1.0) * 100.0),
+            _ => None,
+        }
+    }
+
+    pub fn is_regression(&self, threshold: f64) -> bool {
+        self.percent().is_some_and(|p| p > threshold)
+    }
+}
+
+pub fn compare(baseline: &BTreeMap<String, f64>, current: &BTreeMap<String, f64>) -> Vec<Change> {
+    let mut ids: Vec<&String> = baseline.keys().chain(current.keys()).collect();
+    ids.sort();
+    ids.dedup();
+    ids.into_iter()
+        .map(|id| Change {
+            id: id.clone(),
+            baseline: baseline.get(id).copied(),
+            current: current.get(id).copied(),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solutions::{find_event, DEFAULT_EVENT};
+
+    #[test]
+    fn test_stats() {
+        let samples = [4, 1, 3, 2].map(Duration::from_nanos);
+        let stats = Stats::from_samples(&samples);
+        assert_eq!(stats.min, 1.0);
+        assert_eq!(stats.median, 2.5);
+        assert_eq!(stats.mean, 2.5);
+        assert!((stats.std_dev - 1.2910).abs() < 1e-4);
+        let (lower, upper) = stats.confidence_interval();
+        assert!((lower - 1.2348).abs() < 1e-4);
+        assert!((upper - 3.7652).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_criterion_record() {
+        let puzzle = find_event(DEFAULT_EVENT)
+            .and_then(|e| e.find_puzzle(2, 1))
+            .unwrap();
+        let stats = bench(puzzle, "src/inputs/day02e.txt", &Params::default(), 1, 5).unwrap();
+        let allocations = AllocStats {
+            count: 3,
+            bytes: 1024,
+            peak: 512,
+        };
+        let record = criterion_record(&bench_id(puzzle), &stats, Some(&allocations));
+        assert_eq!(record["allocations"]["peak"], 512);
+        assert_eq!(record["reason"], "benchmark-complete");
+        assert_eq!(record["id"], "Solutions/Day 02/Part 01");
+        assert_eq!(record["typical"]["unit"], "ns");
+        assert!(record["typical"]["estimate"].as_f64().unwrap() >= stats.min);
+        assert!(
+            record["typical"]["lower_bound"].as_f64().unwrap()
+                <= record["typical"]["upper_bound"].as_f64().unwrap()
+        );
+        assert_eq!(
+            parse_record(&record.to_string()),
+            Some((String::from("Solutions/Day 02/Part 01"), stats.mean))
+        );
+    }
+
+    #[test]
+    fn test_compare() {
+        let line = "{\"reason\":\"benchmark-complete\",\"id\":\"Solutions/Day 01/Part 01\",\"typical\":{\"estimate\":40.0,\"unit\":\"us\"}}";
+        assert_eq!(
+            parse_record(line),
+            Some((String::from("Solutions/Day 01/Part 01"), 40000.0))
+        );
+        assert_eq!(parse_record("{\"reason\":\"group-complete\"}"), None);
+        let baseline = BTreeMap::from([(String::from("a"), 100.0), (String::from("b"), 100.0)]);
+        let current = BTreeMap::from([(String::from("a"), 125.0), (String::from("c"), 100.0)]);
+        let changes = compare(&baseline, &current);
+        assert_eq!(changes.len(), 3);
+        assert_eq!(changes[0].percent(), Some(25.0));
+        assert!(changes[0].is_regression(10.0));
+        assert!(!changes[0].is_regression(30.0));
+        assert_eq!(changes[1].current, None);
+        assert_eq!(changes[2].percent(), None);
+    }
+}
@@ -0,0 +1,80 @@
+use std::{process, time::Duration};
+
+use aoc_2023::bench::{compare, load_results};
+
+const DEFAULT_THRESHOLD: f64 = 10.0;
+
+struct Args {
+    baseline: String,
+    current: String,
+    threshold: f64,
+}
+
+fn parse_args() -> Result<Args, pico_args::Error> {
+    let mut args = pico_args::Arguments::from_env();
+    let threshold = args
+        .opt_value_from_str(["-t", "--threshold"])?
+        .unwrap_or(DEFAULT_THRESHOLD);
+    Ok(Args {
+        baseline: args.free_from_str()?,
+        current: args.free_from_str()?,
+        threshold,
+    })
+}
+
+fn format_time(nanos: Option<f64>) -> String {
+    match nanos {
+        Some(n) => format!("{:.2?}", Duration::from_nanos(n as u64)),
+        None => String::from("-"),
+    }
+}
+
+fn main() {
+    let args = match parse_args() {
+        Ok(args) => args,
+        Err(_) => {
+            eprintln!("Need to specify a baseline and a new results directory, and optionally `--threshold <percent>`. example: `cargo bench_compare bench_results/old bench_results`");
+            process::exit(1);
+        }
+    };
+    let load = |dir: &str| {
+        load_results(dir).unwrap_or_else(|e| {
+            eprintln!("Failed to load results: {}", e);
+            process::exit(1);
+        })
+    };
+    let changes = compare(&load(&args.baseline), &load(&args.current));
+
+    println!("| Benchmark | Baseline | New | Change |");
+    println!("|:----------|---------:|----:|-------:|");
+    let mut regressions = 0;
+    for change in &changes {
+        let name = change.id.strip_prefix("Solutions/").unwrap_or(&change.id);
+        let percent = match change.percent() {
+            Some(p) if change.is_regression(args.threshold) => {
+                regressions += 1;
+                format!("**{:+.1}%**", p)
+            }
+            Some(p) => format!("{:+.1}%", p),
+            None if change.baseline.is_none() => String::from("new"),
+            None => String::from("removed"),
+        };
+        println!(
+            "| {} | {} | {} | {} |",
+            name,
+            format_time(change.baseline),
+            format_time(change.current),
+            percent
+        );
+    }
+    println!();
+    println!(
+        "{} of {} benchmarks regressed by more than {:.1}%",
+        regressions,
+        changes.len(),
+        args.threshold
+    );
+    if regressions > 0 {
+        process::exit(1);
+    }
+}
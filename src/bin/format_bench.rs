@@ -1,206 +1,802 @@
 use std::{
     collections::HashMap,
-    fs::{self, File, OpenOptions},
-    io::{self, BufRead, Write},
+    fs,
+    io::{self, BufRead},
+    process,
 };
 
-const TOP_TEMPLATE: &str = r###"## Runtime
+use aoc_2023::{
+    alloc::format_bytes,
+    bench::to_nanos,
+    solutions::{find_event, DEFAULT_EVENT, DEFAULT_ROOT},
+    utils::args::{self, ArgsError},
+};
+
+// The table goes between these, everything around them is left alone.
+const BEGIN_MARKER: &str = "<!-- BENCH:BEGIN -->";
+const END_MARKER: &str = "<!-- BENCH:END -->";
 
-| Day/Part | Part 1 | Part 2 | Total |
-|:---------|-------:|-------:|------:|
-"###;
+const DEFAULT_FILE: &str = "README.md";
 
 #[derive(Debug, Clone)]
 struct BenchData {
     id: String,
-    value: f64,
-    unit: String,
+    estimate: Estimate,
 }
 
-#[derive(Debug, Clone)]
+// A duration, in nanoseconds.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
 struct Value {
-    value: f64,
-    unit: String,
+    nanos: f64,
+}
+
+// The time of one part, with the bounds of its confidence interval and its median when known.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+struct Estimate {
+    typical: Value,
+    lower: Option<Value>,
+    upper: Option<Value>,
+    median: Option<Value>,
+    allocations: Option<Allocations>,
+}
+
+// What one run allocated, as reported by `cargo solve_alloc --bench --json`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct Allocations {
+    count: u64,
+    bytes: u64,
+    peak: u64,
+}
+
+// A CSV or JSON value, times and rates keep their fractions, counts don't have any.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Number {
+    Float(f64),
+    Count(u64),
+}
+
+impl std::fmt::Display for Number {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Number::Float(v) => write!(f, "{:.2}", v),
+            Number::Count(v) => write!(f, "{}", v),
+        }
+    }
+}
+
+impl Number {
+    fn to_json(self) -> serde_json::Value {
+        match self {
+            Number::Float(v) => serde_json::json!(v),
+            Number::Count(v) => serde_json::json!(v),
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
 struct Row {
     day: String,
-    part1: Option<Value>,
-    part2: Option<Value>,
+    parse: Option<Estimate>,
+    part1: Option<Estimate>,
+    part2: Option<Estimate>,
+    input_bytes: Option<u64>,
 }
 
-const UNITS: &[&str] = &["s", "ms", "us", "ns", "ps"];
+// The optional columns shown for each part, and whether parsing has a column of its own.
+#[derive(Debug, Clone, Copy, Default)]
+struct Columns {
+    parse: bool,
+    ci: bool,
+    median: bool,
+    throughput: bool,
+    alloc: bool,
+}
 
-fn add_durations(v1: &Value, v2: &Value) -> Value {
-    if v1.unit == v2.unit {
+// The units times are printed in, each a thousand times the previous one.
+const DISPLAY_UNITS: &[&str] = &["ps", "ns", "μs", "ms", "s"];
+
+impl Value {
+    fn parse(value: f64, unit: &str) -> Option<Self> {
+        Some(Value {
+            nanos: to_nanos(value, unit)?,
+        })
+    }
+}
+
+impl std::ops::Add for Value {
+    type Output = Value;
+
+    fn add(self, other: Value) -> Value {
         Value {
-            value: v1.value + v2.value,
-            unit: v1.unit.clone(),
-        }
-    } else {
-        let v1_unit_pos = UNITS.iter().position(|r| r == &v1.unit.clone()).unwrap();
-        let v2_unit_pos = UNITS.iter().position(|r| r == &v2.unit.clone()).unwrap();
-        if v1_unit_pos > v2_unit_pos {
-            Value {
-                value: (v1.value / (1000.0_f64.powf((v1_unit_pos - v2_unit_pos) as f64)))
-                    + v2.value,
-                unit: v2.unit.clone(),
-            }
-        } else {
-            Value {
-                value: (v2.value / (1000.0_f64.powf((v2_unit_pos - v1_unit_pos) as f64)))
-                    + v1.value,
-                unit: v1.unit.clone(),
-            }
+            nanos: self.nanos + other.nanos,
         }
     }
 }
 
 fn format_time(v: &Value) -> String {
-    let (mut unit, mut value) = (v.unit.clone(), v.value);
-    while value > 1000.0 {
+    let (mut value, mut unit) = if v.nanos > 0.0 && v.nanos < 1.0 {
+        (v.nanos * 1000.0, 0)
+    } else {
+        (v.nanos, 1)
+    };
+    while value > 1000.0 && unit + 1 < DISPLAY_UNITS.len() {
         value /= 1000.0;
-        unit = match unit.as_str() {
-            "ms" => "s".to_string(),
-            "us" => "ms".to_string(),
-            "ns" => "us".to_string(),
-            "ps" => "ns".to_string(),
-            _ => "".to_string(),
+        unit += 1;
+    }
+    format!("{:.2}{}", value, DISPLAY_UNITS[unit])
+}
+
+fn format_throughput(bytes_per_second: f64) -> String {
+    format!("{}/s", format_bytes(bytes_per_second as usize))
+}
+
+fn format_allocations(a: &Allocations) -> String {
+    format!(
+        "{} / {} (peak {})",
+        a.count,
+        format_bytes(a.bytes as usize),
+        format_bytes(a.peak as usize)
+    )
+}
+
+fn typical(part: &Option<Estimate>) -> Option<Value> {
+    part.map(|e| e.typical)
+}
+
+// A day with a single part, like the last one, only counts that part. Parsing is counted once.
+fn row_total(r: &Row) -> Value {
+    typical(&r.parse).unwrap_or_default()
+        + typical(&r.part1).unwrap_or_default()
+        + typical(&r.part2).unwrap_or_default()
+}
+
+// How many bytes of the input a part goes through each second.
+fn throughput(r: &Row, part: &Option<Estimate>) -> Option<f64> {
+    let nanos = typical(part)?.nanos;
+    match r.input_bytes {
+        Some(bytes) if nanos > 0.0 => Some(bytes as f64 / (nanos / 1e9)),
+        _ => None,
+    }
+}
+
+// The optional columns of a part by name, times in nanoseconds and throughput in bytes per second.
+fn extra_values(
+    r: &Row,
+    part: &Option<Estimate>,
+    columns: Columns,
+) -> Vec<(&'static str, Option<Number>)> {
+    let nanos = |v: Option<Value>| v.map(|v| Number::Float(v.nanos));
+    let mut values = Vec::new();
+    if columns.ci {
+        values.push(("lower_ns", nanos(part.and_then(|e| e.lower))));
+        values.push(("upper_ns", nanos(part.and_then(|e| e.upper))));
+    }
+    if columns.median {
+        values.push(("median_ns", nanos(part.and_then(|e| e.median))));
+    }
+    if columns.throughput {
+        values.push(("bytes_per_s", throughput(r, part).map(Number::Float)));
+    }
+    if columns.alloc {
+        let allocations = part.and_then(|e| e.allocations);
+        let count = |f: fn(&Allocations) -> u64| allocations.as_ref().map(|a| Number::Count(f(a)));
+        values.push(("allocs", count(|a| a.count)));
+        values.push(("alloc_bytes", count(|a| a.bytes)));
+        values.push(("peak_bytes", count(|a| a.peak)));
+    }
+    values
+}
+
+fn extra_cells(r: &Row, part: &Option<Estimate>, columns: Columns) -> Vec<String> {
+    let mut cells = Vec::new();
+    if columns.ci {
+        cells.push(match part.and_then(|e| e.lower.zip(e.upper)) {
+            Some((lower, upper)) => format!("{} – {}", format_time(&lower), format_time(&upper)),
+            None => String::new(),
+        });
+    }
+    if columns.median {
+        let median = part.and_then(|e| e.median);
+        cells.push(median.as_ref().map(format_time).unwrap_or_default());
+    }
+    if columns.throughput {
+        cells.push(
+            throughput(r, part)
+                .map(format_throughput)
+                .unwrap_or_default(),
+        );
+    }
+    if columns.alloc {
+        let allocations = part.and_then(|e| e.allocations);
+        cells.push(
+            allocations
+                .as_ref()
+                .map(format_allocations)
+                .unwrap_or_default(),
+        );
+    }
+    cells
+}
+
+fn format_header(columns: Columns) -> String {
+    let mut names = vec![String::from("Day/Part")];
+    if columns.parse {
+        names.push(String::from("Parse"));
+    }
+    for part in ["Part 1", "Part 2"] {
+        names.push(part.to_string());
+        if columns.ci {
+            names.push(format!("{} CI", part));
+        }
+        if columns.median {
+            names.push(format!("{} Median", part));
+        }
+        if columns.throughput {
+            names.push(format!("{} Throughput", part));
+        }
+        if columns.alloc {
+            names.push(format!("{} Allocs", part));
         }
     }
-    if unit == *"us" {
-        unit = "μs".to_string()
+    names.push(String::from("Total"));
+    let alignments: Vec<String> = names
+        .iter()
+        .enumerate()
+        .map(|(i, n)| match i {
+            0 => format!(":{}", "-".repeat(n.len() + 1)),
+            _ => format!("{}:", "-".repeat(n.len() + 1)),
+        })
+        .collect();
+    format!("| {} |\n|{}|\n", names.join(" | "), alignments.join("|"))
+}
+
+fn format_row(r: &Row, writer: &mut String, columns: Columns) -> Value {
+    let mut cells = vec![format!("**{}**", r.day)];
+    if columns.parse {
+        cells.push(
+            typical(&r.parse)
+                .as_ref()
+                .map(format_time)
+                .unwrap_or_default(),
+        );
+    }
+    for part in [&r.part1, &r.part2] {
+        cells.push(typical(part).as_ref().map(format_time).unwrap_or_default());
+        cells.extend(extra_cells(r, part, columns));
+    }
+    let total = row_total(r);
+    cells.push(format_time(&total));
+    writer.push_str(&format!("| {} |\n", cells.join(" | ")));
+    total
+}
+
+impl BenchData {
+    // Lines about anything but a finished benchmark give None, broken ones an error.
+    fn from_string(line: &str) -> Result<Option<Self>, String> {
+        let json: serde_json::Value =
+            serde_json::from_str(line).map_err(|e| format!("not JSON ({})", e))?;
+        if json.get("reason").and_then(|r| r.as_str()) != Some("benchmark-complete") {
+            return Ok(None);
+        }
+        let id = json["id"].as_str().ok_or("no id")?;
+        let estimate = json["typical"]["estimate"]
+            .as_f64()
+            .ok_or("no typical estimate")?;
+        let unit = json["typical"]["unit"].as_str().ok_or("no typical unit")?;
+        let typical =
+            Value::parse(estimate, unit).ok_or_else(|| format!("unknown unit \"{}\"", unit))?;
+        // The rest is optional, and shares the unit of its own object.
+        let optional = |object: &str, field: &str| {
+            let unit = json[object]["unit"].as_str()?;
+            Value::parse(json[object][field].as_f64()?, unit)
+        };
+        Ok(Some(BenchData {
+            id: id.to_string(),
+            estimate: Estimate {
+                typical,
+                lower: optional("typical", "lower_bound"),
+                upper: optional("typical", "upper_bound"),
+                median: optional("median", "estimate"),
+                allocations: json.get("allocations").and_then(|a| {
+                    Some(Allocations {
+                        count: a["count"].as_u64()?,
+                        bytes: a["bytes"].as_u64()?,
+                        peak: a["peak"].as_u64()?,
+                    })
+                }),
+            },
+        }))
     }
 
-    format!("{:.2}{}", value, unit)
+    // "Solutions/Day 01/Part 02" is day "Day 01", part 2, and "Solutions/Day 01/Parse" part 0.
+    fn day_and_part(&self) -> Option<(&str, u8)> {
+        let mut parts = self.id.split('/').skip(1);
+        let day = parts.next()?;
+        let part = match parts.next()? {
+            "Parse" => 0,
+            "Part 01" => 1,
+            "Part 02" => 2,
+            _ => return None,
+        };
+        Some((day, part))
+    }
 }
 
-fn format_row(r: &Row, writer: &mut File) -> Value {
-    let mut times = Vec::new();
-    let part_1 = match &r.part1 {
-        Some(v) => {
-            times.push(v);
-            format_time(v)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Format {
+    Markdown,
+    Csv,
+    Json,
+    Chart,
+}
+
+impl std::str::FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "markdown" | "md" => Ok(Format::Markdown),
+            "csv" => Ok(Format::Csv),
+            "json" => Ok(Format::Json),
+            "chart" => Ok(Format::Chart),
+            _ => Err(format!(
+                "unknown format \"{}\", expected markdown, csv, json or chart",
+                s
+            )),
         }
-        None => "".to_string(),
+    }
+}
+
+struct Args {
+    event: String,
+    root: String,
+    file: String,
+    output: Option<String>,
+    dry_run: bool,
+    format: Format,
+    columns: Columns,
+}
+
+fn parse_args() -> Result<Args, ArgsError> {
+    let mut args = pico_args::Arguments::from_env();
+    let parsed = Args {
+        event: args
+            .opt_value_from_str(["-y", "--event"])?
+            .unwrap_or_else(|| DEFAULT_EVENT.to_string()),
+        root: args
+            .opt_value_from_str("--root")?
+            .unwrap_or_else(|| DEFAULT_ROOT.to_string()),
+        dry_run: args.contains("--dry-run"),
+        columns: Columns {
+            // Known once the results are read.
+            parse: false,
+            ci: args.contains("--ci"),
+            median: args.contains("--median"),
+            throughput: args.contains("--throughput"),
+            alloc: args.contains("--alloc"),
+        },
+        format: args
+            .opt_value_from_str("--format")?
+            .unwrap_or(Format::Markdown),
+        output: args.opt_value_from_str(["-o", "--output"])?,
+        file: args
+            .opt_value_from_str(["-f", "--file"])?
+            .unwrap_or_else(|| DEFAULT_FILE.to_string()),
     };
+    args::finish(args)?;
+    Ok(parsed)
+}
+
+// The markers have to be on lines of their own, so mentioning them elsewhere is fine.
+fn replace_between_markers(document: &str, table: &str) -> Option<String> {
+    let lines: Vec<&str> = document.split_inclusive('\n').collect();
+    let begin = lines.iter().position(|l| l.trim() == BEGIN_MARKER)?;
+    let end = begin + lines[begin..].iter().position(|l| l.trim() == END_MARKER)?;
+    Some(format!(
+        "{}{}{}",
+        lines[..=begin].concat(),
+        table,
+        lines[end..].concat()
+    ))
+}
+
+fn format_table(values: &[&Row], columns: Columns) -> String {
+    let mut table = format_header(columns);
+    let mut total = None;
+    for v in values {
+        let v_total = format_row(v, &mut table, columns);
+        total = Some(total.map_or(v_total, |t| t + v_total));
+    }
+    if let Some(t) = total {
+        table.push_str(&format!("\n**Total runtime: {}**\n", format_time(&t)));
+    }
+    table
+}
 
-    let part_2 = match &r.part2 {
-        Some(v) => {
-            times.push(v);
-            format_time(v)
+// Every column of a row by name, for CSV and JSON.
+fn row_values(r: &Row, columns: Columns) -> Vec<(String, Option<Number>)> {
+    let mut values = Vec::new();
+    if columns.parse {
+        let nanos = typical(&r.parse).map(|v| Number::Float(v.nanos));
+        values.push((String::from("parse_ns"), nanos));
+    }
+    for (name, part) in [("part1", &r.part1), ("part2", &r.part2)] {
+        let nanos = typical(part).map(|v| Number::Float(v.nanos));
+        values.push((format!("{}_ns", name), nanos));
+        for (extra, value) in extra_values(r, part, columns) {
+            values.push((format!("{}_{}", name, extra), value));
         }
-        None => "".to_string(),
-    };
-    let total = if times.len() < 2 {
-        times[0].clone()
-    } else {
-        add_durations(times[0], times[1])
+    }
+    let total = Number::Float(row_total(r).nanos);
+    values.push((String::from("total_ns"), Some(total)));
+    values
+}
+
+// Times are in nanoseconds, a part that wasn't benchmarked is left empty.
+fn format_csv(values: &[&Row], columns: Columns) -> String {
+    let Some(first) = values.first() else {
+        return String::new();
     };
-    writer
-        .write_all(
-            format!(
-                "| **{}** | {} | {} | {} |\n",
-                r.day,
-                part_1,
-                part_2,
-                format_time(&total)
-            )
-            .as_bytes(),
-        )
-        .unwrap();
-    total
+    let names: Vec<String> = row_values(first, columns)
+        .into_iter()
+        .map(|(n, _)| n)
+        .collect();
+    let mut csv = format!("day,{}\n", names.join(","));
+    for r in values {
+        let cells: Vec<String> = row_values(r, columns)
+            .into_iter()
+            .map(|(_, v)| v.map(|v| v.to_string()).unwrap_or_default())
+            .collect();
+        csv.push_str(&format!("{},{}\n", r.day, cells.join(",")));
+    }
+    csv
 }
 
-impl BenchData {
-    fn from_string(line: &str) -> Option<Self> {
-        let json: serde_json::Value = serde_json::from_str(line).unwrap();
-        if json.get("reason").unwrap() == "benchmark-complete" {
-            return Some(BenchData {
-                id: json["id"].as_str().unwrap().to_string(),
-                value: json["typical"]["estimate"].as_f64().unwrap(),
-                unit: json["typical"]["unit"].as_str().unwrap().to_string(),
-            });
-        }
+fn format_json(values: &[&Row], columns: Columns) -> String {
+    let rows: Vec<serde_json::Value> = values
+        .iter()
+        .map(|r| {
+            let mut row = serde_json::Map::new();
+            row.insert(String::from("day"), serde_json::json!(r.day));
+            for (name, value) in row_values(r, columns) {
+                let value = value.map_or(serde_json::Value::Null, Number::to_json);
+                row.insert(name, value);
+            }
+            serde_json::Value::Object(row)
+        })
+        .collect();
+    format!("{}\n", serde_json::Value::Array(rows))
+}
+
+const CHART_WIDTH: usize = 50;
 
-        None
+// One bar per day. Days take anything from microseconds to seconds, so the bars are on a log
+// scale going from the fastest day to the slowest one.
+fn format_chart(values: &[&Row]) -> String {
+    let totals: Vec<f64> = values
+        .iter()
+        .map(|r| row_total(r).nanos.max(1.0).ln())
+        .collect();
+    let min = totals.iter().copied().fold(f64::INFINITY, f64::min);
+    let max = totals.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    let mut chart = String::new();
+    for (r, total) in values.iter().zip(&totals) {
+        let width = if max > min {
+            1 + ((total - min) / (max - min) * (CHART_WIDTH - 1) as f64).round() as usize
+        } else {
+            CHART_WIDTH
+        };
+        chart.push_str(&format!(
+            "{} | {:<w$} | {}\n",
+            r.day,
+            "#".repeat(width),
+            format_time(&row_total(r)),
+            w = CHART_WIDTH
+        ));
     }
+    chart
 }
 
 fn main() {
+    let mut args = match parse_args() {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("{}", e);
+            eprintln!("Reads benchmark results from stdin. Optionally `--file <path>` to update instead of README.md, `--output <path>` to write elsewhere, or `--dry-run` to print the result. `--format <csv|json|chart>` prints the results in another format, or writes them to `--output <path>`. `--ci`, `--median`, `--throughput` and `--alloc` add columns, throughput reading the inputs of `--event <name>` under `--root <dir>`.");
+            process::exit(1);
+        }
+    };
     let lines = io::stdin().lock().lines();
-    let mut rows = HashMap::new();
-    for line in lines {
-        if let Ok(line_content) = line {
-            let b_data = BenchData::from_string(&line_content);
-            if let Some(row_data) = b_data {
-                let mut parts = row_data.id.split('/').skip(1);
-                let day = parts.next().unwrap().to_owned();
-                let part = parts.next().unwrap().to_owned();
-                let row = rows.entry(day.clone()).or_insert_with(|| {
-                    if part == "Part 01" {
-                        Row {
-                            day: day.clone(),
-                            part1: Some(Value {
-                                value: row_data.value,
-                                unit: row_data.unit.clone(),
-                            }),
-                            part2: None,
-                        }
-                    } else {
-                        Row {
-                            day: day.to_string(),
-                            part2: Some(Value {
-                                value: row_data.value,
-                                unit: row_data.unit.clone(),
-                            }),
-                            part1: None,
-                        }
-                    }
-                });
-                if part == "Part 01" {
-                    row.part1 = Some(Value {
-                        value: row_data.value,
-                        unit: row_data.unit.clone(),
-                    });
-                } else {
-                    row.part2 = Some(Value {
-                        value: row_data.value,
-                        unit: row_data.unit.clone(),
-                    });
-                }
+    let mut rows: HashMap<String, Row> = HashMap::new();
+    for (index, line) in lines.enumerate() {
+        let Ok(line_content) = line else {
+            continue;
+        };
+        let row_data = match BenchData::from_string(&line_content) {
+            Ok(Some(row_data)) => row_data,
+            Ok(None) => continue,
+            Err(e) => {
+                eprintln!("Skipping line {}: {}", index + 1, e);
+                continue;
             }
-        } else {
+        };
+        let Some((day, part)) = row_data.day_and_part() else {
+            eprintln!(
+                "Skipping line {}: unexpected id \"{}\"",
+                index + 1,
+                row_data.id
+            );
             continue;
+        };
+        let row = rows.entry(day.to_string()).or_insert_with(|| Row {
+            day: day.to_string(),
+            parse: None,
+            part1: None,
+            part2: None,
+            input_bytes: None,
+        });
+        match part {
+            0 => row.parse = Some(row_data.estimate),
+            1 => row.part1 = Some(row_data.estimate),
+            _ => row.part2 = Some(row_data.estimate),
+        }
+    }
+    // An empty table would wipe out the one already there.
+    if rows.is_empty() {
+        eprintln!("No benchmark results were read from stdin, nothing was written.");
+        process::exit(1);
+    }
+    // Only `cargo bench` times parsing on its own, `cargo solve --bench` includes it in each part.
+    args.columns.parse = rows.values().any(|r| r.parse.is_some());
+    // Throughput is measured against the inputs of the event the results are for.
+    if args.columns.throughput {
+        let Some(event) = find_event(&args.event) else {
+            eprintln!("Unknown event \"{}\".", args.event);
+            process::exit(1);
+        };
+        let root = args.root.trim_end_matches('/');
+        for row in rows.values_mut() {
+            let Ok(day) = row.day.trim_start_matches("Day ").parse() else {
+                continue;
+            };
+            let path = event.input_path(root, day);
+            row.input_bytes = fs::metadata(path).ok().map(|m| m.len());
         }
     }
-    let read_me_content = fs::read_to_string("README.md").unwrap_or_else(|_| "".to_string());
-    let read_me = read_me_content.split("## Runtime").next().unwrap();
-    let mut writer = OpenOptions::new()
-        .write(true)
-        .create(true)
-        .truncate(true)
-        .open("README.md")
-        .unwrap();
-    writer.write_all(read_me.as_bytes()).unwrap();
-    writer.write_all(TOP_TEMPLATE.as_bytes()).unwrap();
     let mut values: Vec<&Row> = rows.values().collect();
     values.sort_by(|a, b| a.day.cmp(&b.day));
-    let mut total = None;
-    for v in values {
-        let v_total = format_row(v, &mut writer);
-        if total.is_none() {
-            total = Some(v_total.clone());
-        } else {
-            total = Some(add_durations(&total.unwrap(), &v_total));
+
+    // Only the markdown table goes into the README, the others are printed or written as is.
+    let formatted = match args.format {
+        Format::Markdown => None,
+        Format::Csv => Some(format_csv(&values, args.columns)),
+        Format::Json => Some(format_json(&values, args.columns)),
+        Format::Chart => Some(format_chart(&values)),
+    };
+    if let Some(formatted) = formatted {
+        match args.output {
+            Some(output) if !args.dry_run => {
+                if let Err(e) = fs::write(&output, formatted) {
+                    eprintln!("Failed to write \"{}\": {}", output, e);
+                    process::exit(1);
+                }
+            }
+            _ => print!("{}", formatted),
         }
+        return;
     }
-    writer.write_all("\n\n".as_bytes()).unwrap();
-    if let Some(t) = total {
-        writer
-            .write_all(format!("**Total runtime: {}**", format_time(&t)).as_bytes())
-            .unwrap();
-        writer.write_all("\n\n".as_bytes()).unwrap();
+
+    let document = match fs::read_to_string(&args.file) {
+        Ok(document) => document,
+        Err(e) => {
+            eprintln!("Failed to read \"{}\": {}", args.file, e);
+            process::exit(1);
+        }
+    };
+    let Some(updated) = replace_between_markers(&document, &format_table(&values, args.columns))
+    else {
+        eprintln!(
+            "\"{}\" has no {} ... {} markers to put the table between, nothing was written.",
+            args.file, BEGIN_MARKER, END_MARKER
+        );
+        process::exit(1);
+    };
+    if args.dry_run {
+        print!("{}", updated);
+        return;
+    }
+    let output = args.output.unwrap_or(args.file);
+    if let Err(e) = fs::write(&output, updated) {
+        eprintln!("Failed to write \"{}\": {}", output, e);
+        process::exit(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rows() -> Vec<Row> {
+        let value = |value: f64, unit: &str| {
+            Value::parse(value, unit).map(|typical| Estimate {
+                typical,
+                ..Default::default()
+            })
+        };
+        vec![
+            Row {
+                day: String::from("Day 01"),
+                parse: None,
+                part1: value(40.0, "us"),
+                part2: value(1.5, "ms"),
+                input_bytes: Some(1000),
+            },
+            Row {
+                day: String::from("Day 02"),
+                parse: None,
+                part1: value(500.0, "ns"),
+                part2: None,
+                input_bytes: None,
+            },
+        ]
+    }
+
+    #[test]
+    fn test_formats() {
+        let rows = rows();
+        let values: Vec<&Row> = rows.iter().collect();
+        assert_eq!(
+            format_csv(&values, Columns::default()),
+            "day,part1_ns,part2_ns,total_ns\nDay 01,40000.00,1500000.00,1540000.00\nDay 02,500.00,,500.00\n"
+        );
+        let json: serde_json::Value =
+            serde_json::from_str(&format_json(&values, Columns::default())).unwrap();
+        assert_eq!(json[0]["total_ns"], 1540000.0);
+        assert_eq!(json[1]["part2_ns"], serde_json::Value::Null);
+        let chart = format_chart(&values);
+        let lines: Vec<&str> = chart.lines().collect();
+        assert!(lines[0].starts_with(&format!("Day 01 | {} |", "#".repeat(CHART_WIDTH))));
+        assert!(lines[1].starts_with("Day 02 | # "));
+        assert!(lines[1].ends_with("| 500.00ns"));
+    }
+
+    #[test]
+    fn test_columns() {
+        let mut rows = rows();
+        rows[0].part1 = Some(Estimate {
+            lower: Value::parse(39.0, "us"),
+            upper: Value::parse(41.0, "us"),
+            median: Value::parse(39.5, "us"),
+            ..rows[0].part1.unwrap()
+        });
+        rows[1].part1 = Some(Estimate {
+            allocations: Some(Allocations {
+                count: 7,
+                bytes: 6072,
+                peak: 3096,
+            }),
+            ..rows[1].part1.unwrap()
+        });
+        let values: Vec<&Row> = rows.iter().collect();
+        let columns = Columns {
+            parse: false,
+            ci: true,
+            median: true,
+            throughput: true,
+            alloc: false,
+        };
+        let table = format_table(&values, columns);
+        let lines: Vec<&str> = table.lines().collect();
+        assert_eq!(lines[0], "| Day/Part | Part 1 | Part 1 CI | Part 1 Median | Part 1 Throughput | Part 2 | Part 2 CI | Part 2 Median | Part 2 Throughput | Total |");
+        assert_eq!(lines[2], "| **Day 01** | 40.00μs | 39.00μs – 41.00μs | 39.50μs | 25.00MB/s | 1.50ms |  |  | 666.67KB/s | 1.54ms |");
+        assert_eq!(
+            lines[3],
+            "| **Day 02** | 500.00ns |  |  |  |  |  |  |  | 500.00ns |"
+        );
+        let csv = format_csv(&values, columns);
+        assert!(csv.starts_with("day,part1_ns,part1_lower_ns,part1_upper_ns,part1_median_ns,part1_bytes_per_s,part2_ns,"));
+        assert!(csv.contains("\nDay 01,40000.00,39000.00,41000.00,39500.00,25000000.00,1500000.00,,,,666666.67,1540000.00\n"));
+        let columns = Columns {
+            alloc: true,
+            ..Default::default()
+        };
+        let table = format_table(&values, columns);
+        assert_eq!(
+            table.lines().nth(3),
+            Some("| **Day 02** | 500.00ns | 7 / 6.07KB (peak 3.10KB) |  |  | 500.00ns |")
+        );
+        let csv = format_csv(&values, columns);
+        assert!(csv.contains("\nDay 02,500.00,7,6072,3096,,,,,500.00\n"));
+    }
+
+    #[test]
+    fn test_values() {
+        assert_eq!(Value::parse(1.5, "ms"), Some(Value { nanos: 1500000.0 }));
+        assert_eq!(Value::parse(1.0, "fortnights"), None);
+        assert_eq!(
+            format_time(&Value::parse(39946.57, "ns").unwrap()),
+            "39.95μs"
+        );
+        assert_eq!(format_time(&Value::parse(0.5, "ns").unwrap()), "500.00ps");
+        assert_eq!(format_time(&Value::parse(2000.0, "s").unwrap()), "2000.00s");
+        let row = Row {
+            day: String::from("Day 25"),
+            parse: None,
+            part1: Value::parse(3.0, "ms").map(|typical| Estimate {
+                typical,
+                ..Default::default()
+            }),
+            part2: None,
+            input_bytes: None,
+        };
+        let mut table = String::new();
+        assert_eq!(
+            format_row(&row, &mut table, Columns::default()).nanos,
+            3000000.0
+        );
+        assert_eq!(table, "| **Day 25** | 3.00ms |  | 3.00ms |\n");
+    }
+
+    #[test]
+    fn test_parse_column() {
+        let mut rows = rows();
+        rows[0].parse = Value::parse(60.0, "us").map(|typical| Estimate {
+            typical,
+            ..Default::default()
+        });
+        let values: Vec<&Row> = rows.iter().collect();
+        let columns = Columns {
+            parse: true,
+            ..Default::default()
+        };
+        let table = format_table(&values, columns);
+        let lines: Vec<&str> = table.lines().collect();
+        assert_eq!(lines[0], "| Day/Part | Parse | Part 1 | Part 2 | Total |");
+        assert_eq!(
+            lines[2],
+            "| **Day 01** | 60.00μs | 40.00μs | 1.50ms | 1.60ms |"
+        );
+        assert_eq!(lines[3], "| **Day 02** |  | 500.00ns |  | 500.00ns |");
+        assert!(table.ends_with("**Total runtime: 1.60ms**\n"));
+        assert!(format_csv(&values, columns)
+            .starts_with("day,parse_ns,part1_ns,part2_ns,total_ns\nDay 01,60000.00,"));
+    }
+
+    #[test]
+    fn test_bench_data() {
+        assert!(BenchData::from_string("   Compiling aoc_2023").is_err());
+        assert!(BenchData::from_string("{\"reason\":\"group-complete\"}")
+            .unwrap()
+            .is_none());
+        let line = "{\"reason\":\"benchmark-complete\",\"id\":\"Solutions/Day 07/Part 01 Disordered\",\"typical\":{\"estimate\":1.0,\"unit\":\"ms\"}}";
+        let data = BenchData::from_string(line).unwrap().unwrap();
+        assert_eq!(data.day_and_part(), None);
+        let line = line.replace(" Disordered", "").replace("\"ms\"", "\"h\"");
+        assert_eq!(
+            BenchData::from_string(&line).unwrap_err(),
+            "unknown unit \"h\""
+        );
+    }
+
+    #[test]
+    fn test_replace_between_markers() {
+        let document = "# Title\n<!-- BENCH:BEGIN -->\nold\n<!-- BENCH:END -->\n## Later\n";
+        assert_eq!(
+            replace_between_markers(document, "new\n").unwrap(),
+            "# Title\n<!-- BENCH:BEGIN -->\nnew\n<!-- BENCH:END -->\n## Later\n"
+        );
+        assert_eq!(
+            replace_between_markers("# Title\n## Runtime\n", "new\n"),
+            None
+        );
+        assert_eq!(
+            replace_between_markers("<!-- BENCH:END --><!-- BENCH:BEGIN -->", "new\n"),
+            None
+        );
+        let document = "Put it between `<!-- BENCH:BEGIN -->` and `<!-- BENCH:END -->`.\n<!-- BENCH:BEGIN -->\n<!-- BENCH:END -->\n";
+        assert_eq!(
+            replace_between_markers(document, "new\n").unwrap(),
+            "Put it between `<!-- BENCH:BEGIN -->` and `<!-- BENCH:END -->`.\n<!-- BENCH:BEGIN -->\nnew\n<!-- BENCH:END -->\n"
+        );
     }
 }
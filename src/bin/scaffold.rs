@@ -3,64 +3,345 @@
  * There is no need to edit this file unless you want to change template functionality.
  */
 use std::{
-    fs::{File, OpenOptions},
-    io::Write,
+    fs::{self, File, OpenOptions},
+    io::{self, Write},
+    path::Path,
     process,
 };
 
-const MODULE_TEMPLATE: &str = r###"// Advent of Code 2023 - Day DAY
+use aoc_2023::utils::args::{self, ArgsError};
 
+// One record per line, each parsed on its own.
+const LINES_TEMPLATE: &str = r###"// {TITLE} - Day {DAY}
 
-pub fn solution_day_DAY_01(file_path: String) -> Option<usize> {
-    None
+use super::{read_input, Solution};
+use crate::error::{parse_lines, AocError, Result};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Record(String);
+
+impl std::str::FromStr for Record {
+    type Err = AocError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Ok(Record(s.to_string()))
+    }
+}
+
+pub struct Day{DAY};
+
+impl Solution for Day{DAY} {
+    const DAY: u8 = {DAY_NUMBER};
+    type Input = Vec<Record>;
+    type Output = usize;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse_lines(input, str::parse)
+    }
+
+    fn part1(_input: &Self::Input) -> Result<usize> {
+        Ok(0)
+    }
+
+    fn part2(_input: &Self::Input) -> Result<usize> {
+        Ok(0)
+    }
+}
+"###;
+
+// A rectangular grid of characters.
+const GRID_TEMPLATE: &str = r###"// {TITLE} - Day {DAY}
+
+use derive_deref::Deref;
+
+use super::{read_input, Solution};
+use crate::error::{AocError, Result};
+use crate::utils::collections::Faux2DArray;
+
+#[derive(Debug, Clone, PartialEq, Eq, Deref)]
+pub struct Grid(Faux2DArray<char>);
+
+impl std::str::FromStr for Grid {
+    type Err = AocError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Faux2DArray::from_text(s, |c| ".#".contains(c).then_some(c)).map(Grid)
+    }
+}
+
+pub struct Day{DAY};
+
+impl Solution for Day{DAY} {
+    const DAY: u8 = {DAY_NUMBER};
+    type Input = Grid;
+    type Output = usize;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        input.parse()
+    }
+
+    fn part1(_input: &Self::Input) -> Result<usize> {
+        Ok(0)
+    }
+
+    fn part2(_input: &Self::Input) -> Result<usize> {
+        Ok(0)
+    }
+}
+"###;
+
+// Blocks separated by blank lines, each parsed on its own.
+const SECTIONS_TEMPLATE: &str = r###"// {TITLE} - Day {DAY}
+
+use super::{read_input, Solution};
+use crate::error::{parse_lines, sections, AocError, Result};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Section(Vec<String>);
+
+impl std::str::FromStr for Section {
+    type Err = AocError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Ok(Section(parse_lines(s, |l| Ok(l.to_string()))?))
+    }
+}
+
+pub struct Day{DAY};
+
+impl Solution for Day{DAY} {
+    const DAY: u8 = {DAY_NUMBER};
+    type Input = Vec<Section>;
+    type Output = usize;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        sections(input)
+            .map(|(offset, s)| s.parse().map_err(|e: AocError| e.below(offset)))
+            .collect()
+    }
+
+    fn part1(_input: &Self::Input) -> Result<usize> {
+        Ok(0)
+    }
+
+    fn part2(_input: &Self::Input) -> Result<usize> {
+        Ok(0)
+    }
+}
+"###;
+
+// The part wrappers and tests every built-in template ends with.
+const WRAPPERS_TEMPLATE: &str = r###"
+pub fn solution_day_{DAY}_01(file_path: String) -> Result<usize> {
+    solution_day_{DAY}_01_from_str(&read_input(file_path)?)
 }
 
-pub fn solution_day_DAY_02(file_path: String) -> Option<usize> {
-    None
+pub fn solution_day_{DAY}_01_from_str(input: &str) -> Result<usize> {
+    Day{DAY}::solve_part1(input)
 }
 
+pub fn solution_day_{DAY}_02(file_path: String) -> Result<usize> {
+    solution_day_{DAY}_02_from_str(&read_input(file_path)?)
+}
+
+pub fn solution_day_{DAY}_02_from_str(input: &str) -> Result<usize> {
+    Day{DAY}::solve_part2(input)
+}
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
-    fn test_day_DAY_01() {
-        let file_path: String = String::from("src/inputs/dayDAYe.txt");
-        let result = solution_day_DAY_01(file_path).unwrap();
-        assert_eq!(result, 15);
+    fn test_day_{DAY}_01() {
+{EXAMPLE_TESTS_01}
     }
 
     #[test]
-    fn test_day_DAY_02() {
-        let file_path: String = String::from("src/inputs/dayDAYe.txt");
-        let result = solution_day_DAY_02(file_path).unwrap();
-        assert_eq!(result, 12);
+    fn test_day_{DAY}_02() {
+{EXAMPLE_TESTS_02}
     }
 
     #[test]
     #[ignore]
-    fn output_day_DAY_01() {
-        let file_path: String = String::from("src/inputs/dayDAY.txt");
-        let result = solution_day_DAY_01(file_path);
+    fn output_day_{DAY}_01() {
+        let file_path: String = String::from("{INPUTS}/day{DAY}.txt");
+        let result = solution_day_{DAY}_01(file_path);
         dbg!(result.unwrap());
         assert_eq!(1, 1);
     }
 
     #[test]
     #[ignore]
-    fn output_day_DAY_02() {
-        let file_path: String = String::from("src/inputs/dayDAY.txt");
-        let result = solution_day_DAY_02(file_path);
+    fn output_day_{DAY}_02() {
+        let file_path: String = String::from("{INPUTS}/day{DAY}.txt");
+        let result = solution_day_{DAY}_02(file_path);
         dbg!(result.unwrap());
         assert_eq!(1, 1);
     }
 }
 "###;
 
-fn parse_args() -> Result<u8, pico_args::Error> {
+const TEMPLATES: &[(&str, &str)] = &[
+    ("lines", LINES_TEMPLATE),
+    ("grid", GRID_TEMPLATE),
+    ("sections", SECTIONS_TEMPLATE),
+];
+
+const DEFAULT_TEMPLATE: &str = "lines";
+
+// Files in here are whole modules using the same placeholders, and shadow built-in templates of the same name.
+const USER_TEMPLATES_PATH: &str = "templates";
+
+// The module of an event other than the default one, registering its first day.
+const EVENT_MODULE_TEMPLATE: &str = r###"use super::{read_input, Puzzle, Solution};
+
+pub mod day{DAY};
+
+pub const PUZZLES: &[Puzzle] = &[
+    Puzzle::part1::<day{DAY}::Day{DAY}>(),
+    Puzzle::part2::<day{DAY}::Day{DAY}>(),
+];
+"###;
+
+const DEFAULT_EVENT: &str = "2023";
+const DEFAULT_ROOT: &str = "src";
+
+// Where the files of an event go. The default event uses `solutions` and `inputs` directly,
+// the others get a directory of their own in both, like `solutions/y2024`.
+struct Layout {
+    event: String,
+    dir: String,
+    root: String,
+}
+
+impl Layout {
+    fn new(event: &str, root: &str) -> Result<Self, String> {
+        let is_year = event.chars().all(|c| c.is_ascii_digit());
+        let dir = if event == DEFAULT_EVENT {
+            String::new()
+        } else if is_year && !event.is_empty() {
+            format!("y{}", event)
+        } else if event.starts_with(|c: char| c.is_ascii_lowercase())
+            && event
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+        {
+            event.to_string()
+        } else {
+            return Err(format!(
+                "Invalid event \"{}\", expected a year or a lowercase module name",
+                event
+            ));
+        };
+        Ok(Layout {
+            event: event.to_string(),
+            dir,
+            root: root.trim_end_matches('/').to_string(),
+        })
+    }
+
+    fn is_default(&self) -> bool {
+        self.dir.is_empty()
+    }
+
+    fn title(&self) -> String {
+        match self.event.chars().all(|c| c.is_ascii_digit()) {
+            true => format!("Advent of Code {}", self.event),
+            false => self.event.clone(),
+        }
+    }
+
+    fn subdir(&self, name: &str) -> String {
+        match self.is_default() {
+            true => format!("{}/{}", self.root, name),
+            false => format!("{}/{}/{}", self.root, name, self.dir),
+        }
+    }
+
+    fn solutions_dir(&self) -> String {
+        self.subdir("solutions")
+    }
+
+    fn inputs_dir(&self) -> String {
+        self.subdir("inputs")
+    }
+
+    // Registers the days of the event.
+    fn mod_path(&self) -> String {
+        format!("{}/mod.rs", self.solutions_dir())
+    }
+
+    // Registers the events.
+    fn events_path(&self) -> String {
+        format!("{}/solutions/mod.rs", self.root)
+    }
+
+    // What to add to `cargo solve` and `cargo scaffold` to get here again.
+    fn args(&self) -> String {
+        let mut args = String::new();
+        if !self.is_default() {
+            args.push_str(&format!(" --event {}", self.event));
+        }
+        if self.root != DEFAULT_ROOT {
+            args.push_str(&format!(" --root {}", self.root));
+        }
+        args
+    }
+}
+
+// The expected answers of each part, one per example, empty when a part has none for that example.
+type ExampleAnswers = [Vec<String>; 2];
+
+struct Args {
+    day: u8,
+    event: String,
+    root: String,
+    template: String,
+    examples: usize,
+    answers: ExampleAnswers,
+    undo: bool,
+    force: bool,
+}
+
+fn parse_args() -> Result<Args, ArgsError> {
     let mut args = pico_args::Arguments::from_env();
-    args.free_from_str()
+    let undo = args.contains("--undo");
+    let force = args.contains("--force");
+    let event = args
+        .opt_value_from_str(["-y", "--event"])?
+        .or(args.opt_value_from_str("--year")?)
+        .unwrap_or_else(|| DEFAULT_EVENT.to_string());
+    let root = args
+        .opt_value_from_str("--root")?
+        .unwrap_or_else(|| DEFAULT_ROOT.to_string());
+    let template = args
+        .opt_value_from_str(["-t", "--template"])?
+        .unwrap_or_else(|| DEFAULT_TEMPLATE.to_string());
+    let examples = args.opt_value_from_str("--examples")?.unwrap_or(1);
+    let part1 = args.opt_value_from_fn("--part1", parse_answers)?;
+    let part2 = args.opt_value_from_fn("--part2", parse_answers)?;
+    let day = args.free_from_str()?;
+    args::finish(args)?;
+    Ok(Args {
+        day,
+        event,
+        root,
+        template,
+        examples,
+        answers: [part1.unwrap_or_default(), part2.unwrap_or_default()],
+        undo,
+        force,
+    })
+}
+
+// "142,,281" expects 142 from the first example and 281 from the third.
+fn parse_answers(s: &str) -> Result<Vec<String>, String> {
+    let answers: Vec<String> = s.split(',').map(|a| a.trim().to_string()).collect();
+    match answers.iter().find(|a| a.contains(char::is_whitespace)) {
+        Some(a) => Err(format!("invalid answer \"{}\"", a)),
+        None => Ok(answers),
+    }
 }
 
 fn safe_create_file(path: &str) -> Result<File, std::io::Error> {
@@ -68,65 +349,565 @@ fn safe_create_file(path: &str) -> Result<File, std::io::Error> {
 }
 
 fn create_file(path: &str) -> Result<File, std::io::Error> {
-    OpenOptions::new().write(true).create(true).open(path)
+    OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(path)
 }
 
-fn main() {
-    let day = match parse_args() {
-        Ok(day) => day,
-        Err(_) => {
-            eprintln!("Need to specify a day (as integer). example: `cargo scaffold 7`");
-            process::exit(1);
+fn template_names() -> Vec<String> {
+    let mut names: Vec<String> = TEMPLATES.iter().map(|(n, _)| n.to_string()).collect();
+    if let Ok(entries) = fs::read_dir(USER_TEMPLATES_PATH) {
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.extension().is_some_and(|e| e == "rs") {
+                if let Some(name) = path.file_stem() {
+                    names.push(name.to_string_lossy().to_string());
+                }
+            }
         }
-    };
+    }
+    names.sort();
+    names.dedup();
+    names
+}
 
-    let day_padded = format!("{:02}", day);
+fn load_template(name: &str) -> Result<String, String> {
+    let path = format!("{}/{}.rs", USER_TEMPLATES_PATH, name);
+    if Path::new(&path).exists() {
+        return fs::read_to_string(&path)
+            .map_err(|e| format!("Failed to read \"{}\": {}", path, e));
+    }
+    match TEMPLATES.iter().find(|(n, _)| *n == name) {
+        Some((_, head)) => Ok(format!("{}{}", head, WRAPPERS_TEMPLATE)),
+        None => Err(format!(
+            "Unknown template \"{}\", available: {}",
+            name,
+            template_names().join(", ")
+        )),
+    }
+}
 
-    let input_path = format!("src/inputs/day{}.txt", day_padded);
-    let example_path = format!("src/inputs/day{}e.txt", day_padded);
-    let module_path = format!("src/solutions/day{}.rs", day_padded);
+// "e", "e2", "e3", ...
+fn example_name(index: usize) -> String {
+    match index {
+        0 => String::from("e"),
+        _ => format!("e{}", index + 1),
+    }
+}
 
-    let mut file = match safe_create_file(&module_path) {
-        Ok(file) => file,
-        Err(e) => {
-            eprintln!("Failed to create module file: {}", e);
-            process::exit(1);
+fn example_tests(inputs_dir: &str, day: u8, part: u8, answers: &[String]) -> String {
+    let mut checks: Vec<(usize, &str)> = answers
+        .iter()
+        .enumerate()
+        .filter(|(_, a)| !a.is_empty())
+        .map(|(i, a)| (i, a.as_str()))
+        .collect();
+    // Without a known answer the test fails until the example's answer is filled in.
+    if checks.is_empty() {
+        checks.push((0, if part == 1 { "15" } else { "12" }));
+    }
+    checks
+        .iter()
+        .map(|(i, answer)| {
+            format!(
+                "        let file_path: String = String::from(\"{}/day{:02}{}.txt\");\n        let result = solution_day_{:02}_{:02}(file_path).unwrap();\n        assert_eq!(result, {});",
+                inputs_dir,
+                day,
+                example_name(*i),
+                day,
+                part,
+                answer
+            )
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+fn render_module(template: &str, layout: &Layout, day: u8, answers: &ExampleAnswers) -> String {
+    let inputs_dir = layout.inputs_dir();
+    template
+        .replace(
+            "{EXAMPLE_TESTS_01}",
+            &example_tests(&inputs_dir, day, 1, &answers[0]),
+        )
+        .replace(
+            "{EXAMPLE_TESTS_02}",
+            &example_tests(&inputs_dir, day, 2, &answers[1]),
+        )
+        .replace("{TITLE}", &layout.title())
+        .replace("{INPUTS}", &inputs_dir)
+        .replace("{DAY_NUMBER}", &day.to_string())
+        .replace("{DAY}", &format!("{:02}", day))
+}
+
+// Day of a `pub mod dayNN;` or `Puzzle::partN::<dayNN::DayNN>(),` line.
+fn day_of_line(line: &str) -> Option<u8> {
+    let start = line.find("day")? + 3;
+    line.get(start..start + 2)?.parse().ok()
+}
+
+fn is_module_line(line: &str) -> bool {
+    line.starts_with("pub mod day")
+}
+
+fn is_puzzle_line(line: &str) -> bool {
+    line.trim_start().starts_with("Puzzle::part")
+}
+
+fn puzzle_lines(day: u8) -> [String; 2] {
+    [1, 2].map(|part| {
+        format!(
+            "    Puzzle::part{}::<day{:02}::Day{:02}>(),",
+            part, day, day
+        )
+    })
+}
+
+// Adds `lines` among the ones `is_entry` recognises, keeping them sorted by day.
+// Returns None when the day is already there.
+fn insert_sorted(
+    src: &str,
+    day: u8,
+    lines: &[String],
+    is_entry: fn(&str) -> bool,
+) -> Option<String> {
+    let mut content: Vec<&str> = src.split('\n').collect();
+    if content
+        .iter()
+        .any(|l| is_entry(l) && day_of_line(l) == Some(day))
+    {
+        return None;
+    }
+    let last = content.iter().rposition(|l| is_entry(l))?;
+    let position = content
+        .iter()
+        .position(|l| is_entry(l) && day_of_line(l).is_some_and(|d| d > day))
+        .unwrap_or(last + 1);
+    for (offset, line) in lines.iter().enumerate() {
+        content.insert(position + offset, line);
+    }
+    Some(content.join("\n"))
+}
+
+// Returns None when the day isn't there.
+fn remove_day(src: &str, day: u8, is_entry: fn(&str) -> bool) -> Option<String> {
+    let content: Vec<&str> = src.split('\n').collect();
+    let kept: Vec<&str> = content
+        .iter()
+        .filter(|l| !(is_entry(l) && day_of_line(l) == Some(day)))
+        .copied()
+        .collect();
+    if kept.len() == content.len() {
+        return None;
+    }
+    Some(kept.join("\n"))
+}
+
+fn register(src: &str, day: u8) -> Option<String> {
+    let with_module = insert_sorted(
+        src,
+        day,
+        &[format!("pub mod day{:02};", day)],
+        is_module_line,
+    );
+    let base = with_module.as_deref().unwrap_or(src);
+    match insert_sorted(base, day, &puzzle_lines(day), is_puzzle_line) {
+        Some(registered) => Some(registered),
+        None => with_module,
+    }
+}
+
+fn unregister(src: &str, day: u8) -> Option<String> {
+    let without_module = remove_day(src, day, is_module_line);
+    let base = without_module.as_deref().unwrap_or(src);
+    match remove_day(base, day, is_puzzle_line) {
+        Some(unregistered) => Some(unregistered),
+        None => without_module,
+    }
+}
+
+fn event_line(name: &str, dir: &str) -> String {
+    format!(
+        "    Event::new(\"{}\", \"{}\", {}::PUZZLES),",
+        name, dir, dir
+    )
+}
+
+// Adds an event's module and its entry at the end of `EVENTS`, which may be on a single line.
+fn register_event(src: &str, name: &str, dir: &str) -> Option<String> {
+    let module = format!("pub mod {};", dir);
+    let mut content: Vec<String> = src.split('\n').map(|l| l.to_string()).collect();
+    if content.contains(&module) {
+        return None;
+    }
+    let start = content
+        .iter()
+        .position(|l| l.starts_with("pub const EVENTS"))?;
+    if let Some(entries) = content[start]
+        .split_once("= &[")
+        .and_then(|(_, rest)| rest.strip_suffix("];"))
+    {
+        let head = content[start].split_once("= &[")?.0.to_string();
+        let mut lines = vec![format!("{}= &[", head)];
+        lines.extend(
+            entries
+                .split("), ")
+                .map(|e| format!("    {}),", e.trim_end_matches(',').trim_end_matches(')'))),
+        );
+        lines.push(String::from("];"));
+        content.splice(start..=start, lines);
+    }
+    let end = start + content[start..].iter().position(|l| l == "];")?;
+    content.insert(end, event_line(name, dir));
+    let last_module = content.iter().rposition(|l| l.starts_with("pub mod "))?;
+    content.insert(last_module + 1, module);
+    Some(content.join("\n"))
+}
+
+fn unregister_event(src: &str, name: &str, dir: &str) -> Option<String> {
+    let module = format!("pub mod {};", dir);
+    let event = event_line(name, dir);
+    let content: Vec<&str> = src.split('\n').collect();
+    let mut kept: Vec<String> = content
+        .iter()
+        .filter(|l| **l != module && **l != event)
+        .map(|l| l.to_string())
+        .collect();
+    if kept.len() == content.len() {
+        return None;
+    }
+    // A single event left goes back on one line, the way rustfmt writes it.
+    let start = kept
+        .iter()
+        .position(|l| l.starts_with("pub const EVENTS"))?;
+    if kept.get(start + 2).is_some_and(|l| l == "];") {
+        let entry = kept[start + 1].trim().trim_end_matches(',').to_string();
+        let line = format!("{}{}];", kept[start], entry);
+        if line.len() <= 100 {
+            kept.splice(start..start + 3, [line]);
         }
+    }
+    Some(kept.join("\n"))
+}
+
+fn exit_with(message: String) -> ! {
+    eprintln!("{}", message);
+    process::exit(1);
+}
+
+fn read_file(path: &str) -> String {
+    fs::read_to_string(path)
+        .unwrap_or_else(|e| exit_with(format!("Failed to read \"{}\": {}", path, e)))
+}
+
+fn write_file(path: &str, content: &str) {
+    if let Err(e) = fs::write(path, content) {
+        exit_with(format!("Failed to update \"{}\": {}", path, e));
+    }
+}
+
+fn scaffold(layout: &Layout, day: u8, template: &str, examples: usize, answers: &ExampleAnswers) {
+    let day_padded = format!("{:02}", day);
+    let input_path = format!("{}/day{}.txt", layout.inputs_dir(), day_padded);
+    let examples = examples.max(answers[0].len()).max(answers[1].len()).max(1);
+    let module_path = format!("{}/day{}.rs", layout.solutions_dir(), day_padded);
+    let mod_path = layout.mod_path();
+
+    let template = load_template(template).unwrap_or_else(|e| exit_with(e));
+    if !template.contains("{EXAMPLE_TESTS_01}") && answers.iter().any(|a| !a.is_empty()) {
+        println!("The template has no {{EXAMPLE_TESTS_01}} placeholder, the expected answers are left out");
+    }
+    // A new event starts out with a module registering this day, and an entry in `EVENTS`.
+    let events_content = read_file(&layout.events_path());
+    let new_event = !layout.is_default() && !Path::new(&mod_path).exists();
+    let registered = if new_event {
+        Some(render_module(EVENT_MODULE_TEMPLATE, layout, day, answers))
+    } else {
+        register(&read_file(&mod_path), day)
     };
+    if registered.is_none() && Path::new(&module_path).exists() {
+        exit_with(format!(
+            "Day {} is already registered. Use `cargo scaffold {}{} --undo` to remove it.",
+            day_padded,
+            day,
+            layout.args()
+        ));
+    }
+    for dir in [layout.solutions_dir(), layout.inputs_dir()] {
+        if let Err(e) = fs::create_dir_all(&dir) {
+            exit_with(format!("Failed to create \"{}\": {}", dir, e));
+        }
+    }
 
-    match file.write_all(MODULE_TEMPLATE.replace("DAY", &day_padded).as_bytes()) {
-        Ok(_) => {
-            println!("Created module file \"{}\"", &module_path);
+    // An existing module is kept, so a half-finished scaffold can be completed.
+    match safe_create_file(&module_path) {
+        Ok(mut file) => {
+            match file.write_all(render_module(&template, layout, day, answers).as_bytes()) {
+                Ok(_) => {
+                    println!("Created module file \"{}\"", &module_path);
+                }
+                Err(e) => exit_with(format!("Failed to write module contents: {}", e)),
+            }
         }
-        Err(e) => {
-            eprintln!("Failed to write module contents: {}", e);
-            process::exit(1);
+        Err(e) if e.kind() == io::ErrorKind::AlreadyExists => {
+            println!("Kept existing module file \"{}\"", &module_path);
         }
+        Err(e) => exit_with(format!("Failed to create module file: {}", e)),
     }
 
     match create_file(&input_path) {
         Ok(_) => {
             println!("Created empty input file \"{}\"", &input_path);
         }
-        Err(e) => {
-            eprintln!("Failed to create input file: {}", e);
-            process::exit(1);
-        }
+        Err(e) => exit_with(format!("Failed to create input file: {}", e)),
     }
 
-    match create_file(&example_path) {
-        Ok(_) => {
-            println!("Created empty example file \"{}\"", &example_path);
+    for index in 0..examples {
+        let example_path = format!(
+            "{}/day{}{}.txt",
+            layout.inputs_dir(),
+            day_padded,
+            example_name(index)
+        );
+        match create_file(&example_path) {
+            Ok(_) => {
+                println!("Created empty example file \"{}\"", &example_path);
+            }
+            Err(e) => exit_with(format!("Failed to create example file: {}", e)),
         }
-        Err(e) => {
-            eprintln!("Failed to create example file: {}", e);
-            process::exit(1);
+    }
+
+    if let Some(content) = registered {
+        write_file(&mod_path, &content);
+        println!(
+            "Registered day {} in \"{}\", the runner and benchmarks pick it up from there",
+            &day_padded, mod_path
+        );
+    }
+    if new_event {
+        if let Some(content) = register_event(&events_content, &layout.event, &layout.dir) {
+            write_file(&layout.events_path(), &content);
+            println!(
+                "Registered event {} in \"{}\"",
+                layout.event,
+                layout.events_path()
+            );
         }
     }
 
     println!("---");
     println!(
-        "🎄 Type `cargo solve {}` to run your solution.",
-        &day_padded
+        "🎄 Type `cargo solve {}{}` to run your solution.",
+        &day_padded,
+        layout.args()
     );
 }
+
+fn remove_if(path: &str, removable: bool, reason: &str) {
+    if !Path::new(path).exists() {
+        return;
+    }
+    if !removable {
+        println!("Kept \"{}\" because {}", path, reason);
+        return;
+    }
+    match fs::remove_file(path) {
+        Ok(_) => println!("Removed \"{}\"", path),
+        Err(e) => exit_with(format!("Failed to remove \"{}\": {}", path, e)),
+    }
+}
+
+// The day's real input followed by its examples.
+fn input_files(layout: &Layout, day: u8) -> Vec<String> {
+    let inputs_dir = layout.inputs_dir();
+    let prefix = format!("day{:02}e", day);
+    let mut files = vec![format!("{}/day{:02}.txt", inputs_dir, day)];
+    if let Ok(entries) = fs::read_dir(&inputs_dir) {
+        for entry in entries.flatten() {
+            let name = entry.file_name().to_string_lossy().to_string();
+            let number = name
+                .strip_prefix(&prefix)
+                .and_then(|n| n.strip_suffix(".txt"));
+            if number.is_some_and(|n| n.chars().all(|c| c.is_ascii_digit())) {
+                files.push(format!("{}/{}", inputs_dir, name));
+            }
+        }
+    }
+    files
+}
+
+// The example tests hold the answers given to scaffold, so only the code before them is compared.
+fn solution_code(module: &str) -> &str {
+    module.split("#[cfg(test)]").next().unwrap_or_default()
+}
+
+fn undo(layout: &Layout, day: u8, force: bool) {
+    let day_padded = format!("{:02}", day);
+    let module_path = format!("{}/day{}.rs", layout.solutions_dir(), day_padded);
+    let mod_path = layout.mod_path();
+
+    let mod_content = if Path::new(&mod_path).exists() || layout.is_default() {
+        read_file(&mod_path)
+    } else {
+        String::new()
+    };
+    match unregister(&mod_content, day) {
+        Some(content) => {
+            write_file(&mod_path, &content);
+            println!("Unregistered day {} from \"{}\"", &day_padded, mod_path);
+            // An event without days goes away with its last one.
+            if !layout.is_default() && !content.lines().any(is_module_line) {
+                remove_if(&mod_path, true, "");
+                let events_path = layout.events_path();
+                if let Some(events) =
+                    unregister_event(&read_file(&events_path), &layout.event, &layout.dir)
+                {
+                    write_file(&events_path, &events);
+                    println!(
+                        "Unregistered event {} from \"{}\"",
+                        layout.event, events_path
+                    );
+                }
+            }
+        }
+        None => println!("Day {} was not registered", &day_padded),
+    }
+
+    // Only throw away what scaffold created, unless told otherwise.
+    let untouched = fs::read_to_string(&module_path).is_ok_and(|m| {
+        template_names()
+            .iter()
+            .filter_map(|n| load_template(n).ok())
+            .map(|t| render_module(&t, layout, day, &[vec![], vec![]]))
+            .any(|t| solution_code(&m) == solution_code(&t))
+    });
+    remove_if(
+        &module_path,
+        force || untouched,
+        "it differs from every template, pass --force to remove it anyway",
+    );
+    for path in input_files(layout, day) {
+        let empty = fs::metadata(&path).is_ok_and(|m| m.len() == 0);
+        remove_if(
+            &path,
+            force || empty,
+            "it is not empty, pass --force to remove it anyway",
+        );
+    }
+    if !layout.is_default() {
+        // Only succeeds when nothing else is left in there.
+        let _ = fs::remove_dir(layout.solutions_dir());
+        let _ = fs::remove_dir(layout.inputs_dir());
+    }
+}
+
+fn main() {
+    let args = match parse_args() {
+        Ok(args) => args,
+        Err(e) => {
+            if !e.is_missing() {
+                eprintln!("{}", e);
+            }
+            eprintln!("Need to specify a day (as integer). example: `cargo scaffold 7`, `cargo scaffold 7 --template grid --examples 2 --part1 142 --part2 ,281`, or `cargo scaffold 7 --undo` to remove it. `--event 2024` and `--root <dir>` scaffold another event.");
+            process::exit(1);
+        }
+    };
+
+    let layout = Layout::new(&args.event, &args.root).unwrap_or_else(|e| exit_with(e));
+    if args.undo {
+        undo(&layout, args.day, args.force);
+    } else {
+        scaffold(
+            &layout,
+            args.day,
+            &args.template,
+            args.examples,
+            &args.answers,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MOD: &str = "pub mod day01;\npub mod day03;\n\npub const PUZZLES: &[Puzzle] = &[\n    Puzzle::part1::<day01::Day01>(),\n    Puzzle::part2::<day01::Day01>(),\n    Puzzle::part1::<day03::Day03>(),\n    Puzzle::part2::<day03::Day03>(),\n];\n";
+
+    #[test]
+    fn test_register() {
+        let registered = register(MOD, 2).unwrap();
+        assert_eq!(
+            registered,
+            "pub mod day01;\npub mod day02;\npub mod day03;\n\npub const PUZZLES: &[Puzzle] = &[\n    Puzzle::part1::<day01::Day01>(),\n    Puzzle::part2::<day01::Day01>(),\n    Puzzle::part1::<day02::Day02>(),\n    Puzzle::part2::<day02::Day02>(),\n    Puzzle::part1::<day03::Day03>(),\n    Puzzle::part2::<day03::Day03>(),\n];\n"
+        );
+        assert_eq!(register(&registered, 2), None);
+        assert_eq!(unregister(&registered, 2).as_deref(), Some(MOD));
+        assert_eq!(unregister(MOD, 2), None);
+    }
+
+    #[test]
+    fn test_templates() {
+        for (name, _) in TEMPLATES {
+            let module = render_module(
+                &load_template(name).unwrap(),
+                &Layout::new(DEFAULT_EVENT, DEFAULT_ROOT).unwrap(),
+                7,
+                &[vec![], vec![]],
+            );
+            assert!(module.contains("impl Solution for Day07 {\n    const DAY: u8 = 7;"));
+            assert!(module.contains("fn test_day_07_02()"));
+            assert!(module.starts_with("// Advent of Code 2023 - Day 07"));
+            assert!(module.contains("String::from(\"src/inputs/day07.txt\")"));
+            assert!(!module.contains("{DAY") && !module.contains("{INPUTS}"));
+        }
+        assert!(load_template("unknown").is_err());
+    }
+
+    #[test]
+    fn test_example_tests() {
+        let answers = parse_answers("142,,281").unwrap();
+        assert_eq!(
+            example_tests("src/inputs", 1, 2, &answers),
+            "        let file_path: String = String::from(\"src/inputs/day01e.txt\");\n        let result = solution_day_01_02(file_path).unwrap();\n        assert_eq!(result, 142);\n        let file_path: String = String::from(\"src/inputs/day01e3.txt\");\n        let result = solution_day_01_02(file_path).unwrap();\n        assert_eq!(result, 281);"
+        );
+        assert!(example_tests("src/inputs", 1, 1, &[]).ends_with("assert_eq!(result, 15);"));
+        assert!(parse_answers("1 2").is_err());
+    }
+
+    #[test]
+    fn test_layout() {
+        let layout = Layout::new("2024", "src/").unwrap();
+        assert_eq!(layout.solutions_dir(), "src/solutions/y2024");
+        assert_eq!(layout.inputs_dir(), "src/inputs/y2024");
+        assert_eq!(layout.title(), "Advent of Code 2024");
+        assert_eq!(layout.args(), " --event 2024");
+        let layout = Layout::new("internal", "puzzles").unwrap();
+        assert_eq!(layout.mod_path(), "puzzles/solutions/internal/mod.rs");
+        assert_eq!(layout.events_path(), "puzzles/solutions/mod.rs");
+        assert!(Layout::new("Not a module", "src").is_err());
+    }
+
+    #[test]
+    fn test_register_event() {
+        let events = "pub mod day01;\n\npub const EVENTS: &[Event] = &[Event::new(DEFAULT_EVENT, \"\", PUZZLES)];\n";
+        let registered = register_event(events, "2024", "y2024").unwrap();
+        assert_eq!(
+            registered,
+            "pub mod day01;\npub mod y2024;\n\npub const EVENTS: &[Event] = &[\n    Event::new(DEFAULT_EVENT, \"\", PUZZLES),\n    Event::new(\"2024\", \"y2024\", y2024::PUZZLES),\n];\n"
+        );
+        assert_eq!(register_event(&registered, "2024", "y2024"), None);
+        assert_eq!(
+            unregister_event(&registered, "2024", "y2024").as_deref(),
+            Some(events)
+        );
+    }
+
+    #[test]
+    fn test_register_last() {
+        let registered = register(MOD, 4).unwrap();
+        assert!(registered.starts_with("pub mod day01;\npub mod day03;\npub mod day04;\n"));
+        assert!(registered.ends_with("    Puzzle::part2::<day04::Day04>(),\n];\n"));
+    }
+}
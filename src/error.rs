@@ -0,0 +1,202 @@
+use std::{fmt, str::FromStr};
+
+// Lines and columns are 1-based, like an editor shows them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AocError {
+    Parse {
+        day: Option<u8>,
+        line: usize,
+        column: usize,
+        message: String,
+    },
+    Solve {
+        day: Option<u8>,
+        message: String,
+    },
+    Io {
+        path: String,
+        message: String,
+    },
+}
+
+pub type Result<T> = std::result::Result<T, AocError>;
+
+impl AocError {
+    pub fn parse(line: usize, column: usize, message: impl Into<String>) -> Self {
+        AocError::Parse {
+            day: None,
+            line,
+            column,
+            message: message.into(),
+        }
+    }
+
+    pub fn solve(message: impl Into<String>) -> Self {
+        AocError::Solve {
+            day: None,
+            message: message.into(),
+        }
+    }
+
+    pub fn io(path: &str, err: std::io::Error) -> Self {
+        AocError::Io {
+            path: path.to_string(),
+            message: err.to_string(),
+        }
+    }
+
+    pub fn in_day(self, day: u8) -> Self {
+        match self {
+            AocError::Parse {
+                day: None,
+                line,
+                column,
+                message,
+            } => AocError::Parse {
+                day: Some(day),
+                line,
+                column,
+                message,
+            },
+            AocError::Solve { day: None, message } => AocError::Solve {
+                day: Some(day),
+                message,
+            },
+            e => e,
+        }
+    }
+
+    // Sub-parsers count lines from their own first line, this moves them to where they really are.
+    pub fn below(self, lines: usize) -> Self {
+        match self {
+            AocError::Parse {
+                day,
+                line,
+                column,
+                message,
+            } => AocError::Parse {
+                day,
+                line: line + lines,
+                column,
+                message,
+            },
+            e => e,
+        }
+    }
+}
+
+impl fmt::Display for AocError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AocError::Parse {
+                day,
+                line,
+                column,
+                message,
+            } => {
+                if let Some(day) = day {
+                    write!(f, "Day {:02}, ", day)?;
+                }
+                write!(f, "line {}, column {}: {}", line, column, message)
+            }
+            AocError::Solve { day, message } => {
+                if let Some(day) = day {
+                    write!(f, "Day {:02}: ", day)?;
+                }
+                write!(f, "{}", message)
+            }
+            AocError::Io { path, message } => write!(f, "{}: {}", path, message),
+        }
+    }
+}
+
+impl std::error::Error for AocError {}
+
+// Column of `token` within `line`. `token` has to be a slice of `line`, otherwise it is the first column.
+pub fn column_of(line: &str, token: &str) -> usize {
+    let start = line.as_ptr() as usize;
+    let position = token.as_ptr() as usize;
+    if position < start || position > start + line.len() {
+        return 1;
+    }
+    position - start + 1
+}
+
+pub fn parse_token<T: FromStr>(line: &str, token: &str, what: &str) -> Result<T> {
+    token.parse::<T>().map_err(|_| {
+        AocError::parse(
+            1,
+            column_of(line, token),
+            format!("invalid {} \"{}\"", what, token),
+        )
+    })
+}
+
+pub fn split_token<'a>(line: &str, token: &'a str, delimiter: &str) -> Result<(&'a str, &'a str)> {
+    token.split_once(delimiter).ok_or_else(|| {
+        AocError::parse(
+            1,
+            column_of(line, token) + token.len(),
+            format!("expected \"{}\"", delimiter),
+        )
+    })
+}
+
+pub fn parse_lines<T>(input: &str, parser: impl Fn(&str) -> Result<T>) -> Result<Vec<T>> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(idx, l)| parser(l).map_err(|e| e.below(idx)))
+        .collect()
+}
+
+// Splits on blank lines, along with how many lines come before each section.
+pub fn sections(input: &str) -> impl Iterator<Item = (usize, &str)> {
+    input.split("\n\n").scan(0, |offset, section| {
+        let start = *offset;
+        *offset += section.lines().count() + 1;
+        Some((start, section))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        let e = AocError::parse(3, 7, "invalid number \"x\"").in_day(2);
+        assert_eq!(
+            e.to_string(),
+            "Day 02, line 3, column 7: invalid number \"x\""
+        );
+        let e = AocError::solve("no path found");
+        assert_eq!(e.to_string(), "no path found");
+    }
+
+    #[test]
+    fn test_in_day_keeps_first_day() {
+        let e = AocError::solve("oops").in_day(4).in_day(5);
+        assert_eq!(e.to_string(), "Day 04: oops");
+    }
+
+    #[test]
+    fn test_parse_token() {
+        let line = "Card 1: 41 4x";
+        let token = line.split_whitespace().last().unwrap();
+        let result = parse_token::<usize>(line, token, "number");
+        assert_eq!(result, Err(AocError::parse(1, 12, "invalid number \"4x\"")));
+    }
+
+    #[test]
+    fn test_parse_lines() {
+        let result = parse_lines("1\n2\nx", |l| parse_token::<usize>(l, l, "number"));
+        assert_eq!(result, Err(AocError::parse(3, 1, "invalid number \"x\"")));
+    }
+
+    #[test]
+    fn test_sections() {
+        let offsets: Vec<usize> = sections("a\nb\n\nc\n\nd\ne\n").map(|(o, _)| o).collect();
+        assert_eq!(offsets, vec![0, 3, 5]);
+    }
+}
@@ -1,2 +1,7 @@
+pub mod alloc;
+pub mod answers;
+pub mod bench;
+pub mod error;
 pub mod solutions;
 pub mod utils;
+pub mod watch;
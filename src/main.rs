@@ -1,3 +1,487 @@
+use std::{
+    process,
+    time::{Duration, Instant},
+};
+
+use rayon::prelude::*;
+
+use aoc_2023::{
+    alloc::{self, format_bytes},
+    answers::{answers_path, check_answers, load_answers, Status},
+    bench::{
+        bench, bench_id, criterion_record, measure_allocations, DEFAULT_SAMPLES, DEFAULT_WARMUP,
+    },
+    error::Result,
+    solutions::{
+        find_event, read_input, Answer, Event, Params, Puzzle, DEFAULT_EVENT, DEFAULT_ROOT, EVENTS,
+    },
+    utils::args::{self, ArgsError},
+    watch::watch,
+};
+
+struct DayArgs {
+    day: u8,
+    part: Option<u8>,
+    input: Option<String>,
+    params: Vec<(String, usize)>,
+    json: bool,
+}
+
+enum Command {
+    Day(DayArgs),
+    All(AllArgs),
+    Check(Option<u8>),
+    Watch(u8),
+    Bench(BenchArgs),
+}
+
+// Days run in parallel unless `--sequential`, which times each part without anything else running.
+struct AllArgs {
+    json: bool,
+    sequential: bool,
+    threads: Option<usize>,
+}
+
+struct BenchArgs {
+    day: Option<u8>,
+    samples: usize,
+    warmup: usize,
+    json: bool,
+}
+
+// Which event to run, and the directory holding its `solutions` and `inputs`.
+struct Args {
+    event: String,
+    root: String,
+    command: Command,
+}
+
+fn parse_args() -> std::result::Result<Args, ArgsError> {
+    let mut args = pico_args::Arguments::from_env();
+    let event = args
+        .opt_value_from_str(["-y", "--event"])?
+        .or(args.opt_value_from_str("--year")?)
+        .unwrap_or_else(|| DEFAULT_EVENT.to_string());
+    let root: String = args
+        .opt_value_from_str("--root")?
+        .unwrap_or_else(|| DEFAULT_ROOT.to_string());
+    let root = root.trim_end_matches('/').to_string();
+    let command = parse_command(&mut args)?;
+    args::finish(args)?;
+    Ok(Args {
+        event,
+        root,
+        command,
+    })
+}
+
+fn parse_command(args: &mut pico_args::Arguments) -> std::result::Result<Command, ArgsError> {
+    let json = args.contains("--json");
+    if args.contains("--all") {
+        let sequential = args.contains("--sequential");
+        let threads = args.opt_value_from_str(["-j", "--threads"])?;
+        // Sequential days run on a single thread, so a thread count would be ignored.
+        if sequential && threads.is_some() {
+            return Err(ArgsError::Conflict("--threads", "--sequential"));
+        }
+        return Ok(Command::All(AllArgs {
+            json,
+            sequential,
+            threads,
+        }));
+    }
+    if args.contains("--check") {
+        return Ok(Command::Check(args.opt_free_from_str()?));
+    }
+    if args.contains("--watch") {
+        return Ok(Command::Watch(args.free_from_str()?));
+    }
+    if args.contains("--bench") {
+        let samples = args.opt_value_from_str("--samples")?;
+        let warmup = args.opt_value_from_str("--warmup")?;
+        return Ok(Command::Bench(BenchArgs {
+            day: args.opt_free_from_str()?,
+            samples: samples.unwrap_or(DEFAULT_SAMPLES),
+            warmup: warmup.unwrap_or(DEFAULT_WARMUP),
+            json,
+        }));
+    }
+    let mut input = args.opt_value_from_str("--input")?;
+    if args.contains("--example") {
+        input = Some(String::from("e"));
+    }
+    let params = args.values_from_fn(["-p", "--param"], parse_param)?;
+    let day = args.free_from_str()?;
+    let part = args.opt_free_from_str()?;
+    Ok(Command::Day(DayArgs {
+        day,
+        part,
+        input,
+        params,
+        json,
+    }))
+}
+
+fn parse_param(s: &str) -> std::result::Result<(String, usize), String> {
+    let (name, value) = s
+        .split_once('=')
+        .ok_or_else(|| format!("expected name=value, got \"{}\"", s))?;
+    let value = value
+        .parse()
+        .map_err(|_| format!("invalid value for \"{}\": \"{}\"", name, value))?;
+    Ok((name.to_string(), value))
+}
+
+// "e", "e2", ... name the day's example inputs, anything else is a path.
+fn input_path(event: &Event, root: &str, day: u8, input: Option<&str>) -> String {
+    match input {
+        None => event.input_path(root, day),
+        Some(name) if name.starts_with('e') && name[1..].chars().all(|c| c.is_ascii_digit()) => {
+            event.example_path(root, day, &name[1..])
+        }
+        Some(path) => path.to_string(),
+    }
+}
+
+fn run_day(args: DayArgs, event: &Event, root: &str) {
+    let DayArgs {
+        day,
+        part,
+        input,
+        params,
+        json,
+    } = args;
+    let day_padded = format!("{:02}", day);
+    let input_path = input_path(event, root, day, input.as_deref());
+    let puzzles: Vec<&Puzzle> = match part {
+        Some(p) => event.find_puzzle(day, p).into_iter().collect(),
+        None => event.puzzles_of_day(day).collect(),
+    };
+    if puzzles.is_empty() {
+        match part {
+            Some(p) => eprintln!("Day {} part {} is not implemented.", day_padded, p),
+            None => eprintln!("Day {} is not implemented.", day_padded),
+        }
+        process::exit(1);
+    }
+    let mut known = Params::default();
+    for (name, value) in params {
+        if !puzzles[0].params.iter().any(|p| p.name == name) {
+            let names: Vec<&str> = puzzles[0].params.iter().map(|p| p.name).collect();
+            if names.is_empty() {
+                eprintln!("Day {} takes no parameters.", day_padded);
+            } else {
+                eprintln!(
+                    "Day {} has no parameter \"{}\", it takes: {}.",
+                    day_padded,
+                    name,
+                    names.join(", ")
+                );
+            }
+            process::exit(1);
+        }
+        known.set(&name, value);
+    }
+
+    if json {
+        let mut failed = false;
+        for puzzle in puzzles {
+            let (answer, elapsed) = run_timed(puzzle, &input_path, &known);
+            failed |= answer.is_err();
+            println!("{}", json_report(puzzle, &input_path, &answer, elapsed));
+        }
+        if failed {
+            process::exit(1);
+        }
+        return;
+    }
+
+    let input = match read_input(input_path) {
+        Ok(input) => input,
+        Err(e) => {
+            eprintln!("Failed to read input file {}", e);
+            process::exit(1);
+        }
+    };
+
+    // Every part of a day shares the same parser, so the input is parsed once.
+    let start = Instant::now();
+    let parsed = match puzzles[0].parse(&input) {
+        Ok(parsed) => parsed,
+        Err(e) => {
+            eprintln!("Failed to parse input: {}", e);
+            process::exit(1);
+        }
+    };
+    println!("🎄 Day {} / Parse ({:.2?})", day_padded, start.elapsed());
+
+    let mut failed = false;
+    for puzzle in puzzles {
+        let start = Instant::now();
+        let answer = puzzle.solve_with(parsed.as_ref(), &known);
+        let elapsed = start.elapsed();
+        match answer {
+            Ok(v) => println!(
+                "🎄 Day {} / Part {:02}: {} ({:.2?})",
+                day_padded, puzzle.part, v, elapsed
+            ),
+            Err(e) => {
+                eprintln!("Failed to solve part {:02}: {}", puzzle.part, e);
+                failed = true;
+            }
+        }
+    }
+    if failed {
+        process::exit(1);
+    }
+}
+
+// Times include reading and parsing the input, like the benchmarks in the README.
+fn run_timed(puzzle: &Puzzle, input_path: &str, params: &Params) -> (Result<Answer>, Duration) {
+    let start = Instant::now();
+    let answer = read_input(input_path.to_string())
+        .and_then(|input| puzzle.parse(&input))
+        .and_then(|parsed| puzzle.solve_with(parsed.as_ref(), params));
+    (answer, start.elapsed())
+}
+
+fn json_report(
+    puzzle: &Puzzle,
+    input_path: &str,
+    answer: &Result<Answer>,
+    elapsed: Duration,
+) -> serde_json::Value {
+    serde_json::json!({
+        "day": puzzle.day,
+        "part": puzzle.part,
+        "input": input_path,
+        "answer": answer.as_ref().ok().map(|v| v.to_string()),
+        "duration_ns": elapsed.as_nanos() as u64,
+        "error": answer.as_ref().err().map(|e| e.to_string()),
+    })
+}
+
+// The parts of a day run one after the other, in order.
+fn run_day_timed(
+    event: &Event,
+    root: &str,
+    day: u8,
+) -> Vec<(&'static Puzzle, String, Result<Answer>, Duration)> {
+    let input_path = event.input_path(root, day);
+    event
+        .puzzles_of_day(day)
+        .map(|puzzle| {
+            let (answer, elapsed) = run_timed(puzzle, &input_path, &Params::default());
+            (puzzle, input_path.clone(), answer, elapsed)
+        })
+        .collect()
+}
+
+fn run_all(args: AllArgs, event: &Event, root: &str) {
+    let AllArgs {
+        json,
+        sequential,
+        threads,
+    } = args;
+    let mut days: Vec<u8> = event.puzzles.iter().map(|p| p.day).collect();
+    days.dedup();
+    let start = Instant::now();
+    let (results, threads) = if sequential {
+        let results: Vec<_> = days
+            .iter()
+            .map(|&d| run_day_timed(event, root, d))
+            .collect();
+        (results, 1)
+    } else {
+        let pool = match rayon::ThreadPoolBuilder::new()
+            .num_threads(threads.unwrap_or_default())
+            .build()
+        {
+            Ok(pool) => pool,
+            Err(e) => {
+                eprintln!("Failed to start {:?} threads: {}", threads, e);
+                process::exit(1);
+            }
+        };
+        let results = pool.install(|| {
+            days.par_iter()
+                .map(|&d| run_day_timed(event, root, d))
+                .collect()
+        });
+        (results, pool.current_num_threads())
+    };
+    let wall_time = start.elapsed();
+    if !json {
+        println!("| Day | Part | Answer | Time |");
+        println!("|:----|:-----|-------:|-----:|");
+    }
+    let mut total = Duration::ZERO;
+    let mut failed = false;
+    for (puzzle, input_path, answer, elapsed) in results.into_iter().flatten() {
+        total += elapsed;
+        if json {
+            failed |= answer.is_err();
+            println!("{}", json_report(puzzle, &input_path, &answer, elapsed));
+            continue;
+        }
+        let answer = match answer {
+            Ok(v) => v.to_string(),
+            Err(e) => {
+                failed = true;
+                format!("error: {}", e)
+            }
+        };
+        println!(
+            "| **Day {:02}** | Part {:02} | {} | {:.2?} |",
+            puzzle.day, puzzle.part, answer, elapsed
+        );
+    }
+    if !json {
+        println!();
+        println!("**Total runtime: {:.2?}**", total);
+        if !sequential {
+            println!();
+            println!(
+                "Days ran in parallel on {} thread{} in {:.2?}, use `--sequential` for times measured on their own.",
+                threads,
+                if threads == 1 { "" } else { "s" },
+                wall_time
+            );
+        }
+    }
+    if failed {
+        process::exit(1);
+    }
+}
+
+// JSON lines can be piped into `cargo bench_table` to update the README.
+fn run_bench(args: BenchArgs, event: &Event, root: &str) {
+    let puzzles: Vec<&Puzzle> = match args.day {
+        Some(day) => event.puzzles_of_day(day).collect(),
+        None => event.puzzles.iter().collect(),
+    };
+    if puzzles.is_empty() {
+        eprintln!(
+            "Day {:02} is not implemented.",
+            args.day.unwrap_or_default()
+        );
+        process::exit(1);
+    }
+    if !args.json && alloc::ENABLED {
+        println!("| Day | Part | Min | Median | Mean | Std. Dev. | Allocs | Bytes | Peak |");
+        println!("|:----|:-----|----:|-------:|-----:|----------:|-------:|------:|-----:|");
+    } else if !args.json {
+        println!("| Day | Part | Min | Median | Mean | Std. Dev. |");
+        println!("|:----|:-----|----:|-------:|-----:|----------:|");
+    }
+    let mut failed = false;
+    for puzzle in puzzles {
+        let input_path = event.input_path(root, puzzle.day);
+        let params = Params::default();
+        let measured = bench(puzzle, &input_path, &params, args.warmup, args.samples)
+            .and_then(|stats| Ok((stats, measure_allocations(puzzle, &input_path, &params)?)));
+        let (stats, allocations) = match measured {
+            Ok(measured) => measured,
+            Err(e) => {
+                eprintln!(
+                    "Failed to bench day {:02} part {:02}: {}",
+                    puzzle.day, puzzle.part, e
+                );
+                failed = true;
+                continue;
+            }
+        };
+        if args.json {
+            let record = criterion_record(&bench_id(puzzle), &stats, allocations.as_ref());
+            println!("{}", record);
+            continue;
+        }
+        let time = |ns: f64| format!("{:.2?}", Duration::from_nanos(ns as u64));
+        let allocations = allocations
+            .map(|a| {
+                let (bytes, peak) = (format_bytes(a.bytes), format_bytes(a.peak));
+                format!(" {} | {} | {} |", a.count, bytes, peak)
+            })
+            .unwrap_or_default();
+        println!(
+            "| **Day {:02}** | Part {:02} | {} | {} | {} | {} |{}",
+            puzzle.day,
+            puzzle.part,
+            time(stats.min),
+            time(stats.median),
+            time(stats.mean),
+            time(stats.std_dev),
+            allocations
+        );
+    }
+    if failed {
+        process::exit(1);
+    }
+}
+
+fn check(event: &Event, root: &str, day: Option<u8>) {
+    let path = answers_path(event, root);
+    let answers = match load_answers(&path) {
+        Ok(answers) => answers,
+        Err(e) => {
+            eprintln!("Failed to load answers from {}: {}", path, e);
+            process::exit(1);
+        }
+    };
+    let (mut passed, mut failed, mut missing) = (0, 0, 0);
+    for (expected, status) in check_answers(event, root, &answers, day) {
+        let name = format!(
+            "Day {:02} / Part {:02} / {}",
+            expected.day, expected.part, expected.input
+        );
+        match status {
+            Status::Pass => {
+                passed += 1;
+                println!("PASS    {}: {}", name, expected.answer);
+            }
+            Status::Fail(reason) => {
+                failed += 1;
+                println!("FAIL    {}: {}", name, reason);
+            }
+            Status::Missing => {
+                missing += 1;
+                println!("MISSING {}", name);
+            }
+        }
+    }
+    println!("---");
+    println!("{} passed, {} failed, {} missing", passed, failed, missing);
+    if failed > 0 {
+        process::exit(1);
+    }
+}
+
 fn main() {
-    println!("Hello AOC2023!")
+    let args = match parse_args() {
+        Ok(args) => args,
+        Err(e) => {
+            if !e.is_missing() {
+                eprintln!("{}", e);
+            }
+            eprintln!("Need to specify a day (as integer) and optionally a part, `--all`, `--check [day]`, `--watch <day>` or `--bench [day]`, and optionally `--event <name>`. example: `cargo solve 7 2`");
+            process::exit(1);
+        }
+    };
+    let Some(event) = find_event(&args.event) else {
+        let names: Vec<&str> = EVENTS.iter().map(|e| e.name).collect();
+        eprintln!(
+            "Unknown event \"{}\", registered: {}.",
+            args.event,
+            names.join(", ")
+        );
+        process::exit(1);
+    };
+    let root = args.root.as_str();
+    match args.command {
+        Command::Day(day_args) => run_day(day_args, event, root),
+        Command::All(all_args) => run_all(all_args, event, root),
+        Command::Check(day) => check(event, root, day),
+        Command::Watch(day) => watch(event, root, day),
+        Command::Bench(bench_args) => run_bench(bench_args, event, root),
+    }
 }
@@ -1,28 +1,23 @@
 use regex::Regex;
-use std::fs;
+
+use super::{read_input, Solution};
+use crate::error::{AocError, Result};
 
 // Advent of Code 2023 - Day 01
 
 // A regex-based solution is also possible, but too slow.
-fn get_calibration_value(val: &str) -> u32 {
-    let first = val
-        .chars()
-        .find(|c| c.is_ascii_digit())
-        .unwrap()
-        .to_digit(10)
-        .unwrap();
+fn get_calibration_value(val: &str) -> Option<u32> {
+    let first = val.chars().find(|c| c.is_ascii_digit())?.to_digit(10)?;
     let last = val
         .chars()
         .rev()
-        .find(|c| c.is_ascii_digit())
-        .unwrap()
-        .to_digit(10)
-        .unwrap();
-    (first * 10) + last
+        .find(|c| c.is_ascii_digit())?
+        .to_digit(10)?;
+    Some((first * 10) + last)
 }
 
-fn get_converted_value(s: &str) -> u32 {
-    match s {
+fn get_converted_value(s: &str) -> Option<u32> {
+    let value = match s {
         "one" | "1" => 1,
         "two" | "2" => 2,
         "three" | "3" => 3,
@@ -32,51 +27,85 @@ fn get_converted_value(s: &str) -> u32 {
         "seven" | "7" => 7,
         "eight" | "8" => 8,
         "nine" | "9" => 9,
-        _ => todo!(),
-    }
+        _ => return None,
+    };
+    Some(value)
 }
 
-fn get_calibration_value_extended(val: &str, p: &Regex, rev_p: &Regex) -> u32 {
-    let first = p.find(val).unwrap().as_str();
+fn get_calibration_value_extended(val: &str, p: &Regex, rev_p: &Regex) -> Option<u32> {
+    let first = p.find(val)?.as_str();
     let last: String = rev_p
-        .find(&val.chars().rev().collect::<String>())
-        .unwrap()
+        .find(&val.chars().rev().collect::<String>())?
         .as_str()
         .chars()
         .rev()
         .collect();
-    (get_converted_value(first) * 10) + get_converted_value(&last)
+    Some((get_converted_value(first)? * 10) + get_converted_value(&last)?)
+}
+
+fn sum_lines(input: &[String], calibrate: impl Fn(&str) -> Option<u32>) -> Result<u32> {
+    input
+        .iter()
+        .enumerate()
+        .map(|(idx, l)| {
+            calibrate(l).ok_or_else(|| AocError::parse(idx + 1, 1, "line has no digit"))
+        })
+        .sum()
+}
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    const DAY: u8 = 1;
+    type Input = Vec<String>;
+    type Output = u32;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(input.lines().map(|l| l.to_string()).collect())
+    }
+
+    fn part1(input: &Self::Input) -> Result<u32> {
+        sum_lines(input, get_calibration_value)
+    }
+
+    fn part2(input: &Self::Input) -> Result<u32> {
+        let pattern = Regex::new(r"one|two|three|four|five|six|seven|eight|nine|[1-9]")
+            .expect("Unable to compile regex");
+        let reverse_pattern = Regex::new(r"enin|thgie|neves|xis|evif|ruof|eerht|owt|eno|[1-9]")
+            .expect("Unable to compile regex");
+
+        sum_lines(input, |l| {
+            get_calibration_value_extended(l, &pattern, &reverse_pattern)
+        })
+    }
+}
+
+pub fn solution_day_01_01(file_path: String) -> Result<u32> {
+    solution_day_01_01_from_str(&read_input(file_path)?)
 }
 
-pub fn solution_day_01_01(file_path: String) -> Option<u32> {
-    Some(
-        fs::read_to_string(file_path)
-            .expect("Invalid File")
-            .lines()
-            .map(get_calibration_value)
-            .sum(),
-    )
+pub fn solution_day_01_01_from_str(input: &str) -> Result<u32> {
+    Day01::solve_part1(input)
 }
 
-pub fn solution_day_01_02(file_path: String) -> Option<u32> {
-    let pattern = Regex::new(r"one|two|three|four|five|six|seven|eight|nine|\d")
-        .expect("Unable to compile regex");
-    let reverse_pattern = Regex::new(r"enin|thgie|neves|xis|evif|ruof|eerht|owt|eno|\d")
-        .expect("Unable to compile regex");
-
-    Some(
-        fs::read_to_string(file_path)
-            .expect("Invalid File")
-            .lines()
-            .map(|l| get_calibration_value_extended(l, &pattern, &reverse_pattern))
-            .sum(),
-    )
+pub fn solution_day_01_02(file_path: String) -> Result<u32> {
+    solution_day_01_02_from_str(&read_input(file_path)?)
+}
+
+pub fn solution_day_01_02_from_str(input: &str) -> Result<u32> {
+    Day01::solve_part2(input)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_zero_and_other_digits() {
+        assert_eq!(solution_day_01_02_from_str("a0b7c0"), Ok(77));
+        assert!(solution_day_01_02_from_str("x0٣y").is_err());
+    }
+
     #[test]
     fn test_day_01_01() {
         let file_path: String = String::from("src/inputs/day01e.txt");
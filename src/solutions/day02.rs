@@ -1,30 +1,35 @@
-use std::fs;
-
 use derive_deref::Deref;
+
+use super::{read_input, Solution};
+use crate::error::{column_of, parse_lines, parse_token, split_token, AocError, Result};
+
 // Advent of Code 2023 - Day 02
 #[derive(Deref)]
-struct Game([usize; 3]);
-
-#[derive(Debug, PartialEq, Eq)]
-struct ParseGameError;
+pub struct Game([usize; 3]);
 
 impl std::str::FromStr for Game {
-    type Err = ParseGameError;
+    type Err = AocError;
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (_, turns) = s.split_once(": ").unwrap();
-        let turn_max = turns.split([';', ',']).fold([0; 3], |mut rec, cdef| {
-            let (val, color) = cdef.trim().split_once(' ').unwrap();
-            let val = val.parse::<usize>().unwrap();
+    fn from_str(s: &str) -> Result<Self> {
+        let (_, turns) = split_token(s, s, ": ")?;
+        let mut turn_max = [0; 3];
+        for cdef in turns.split([';', ',']) {
+            let (val, color) = split_token(s, cdef.trim(), " ")?;
+            let val = parse_token::<usize>(s, val, "cube count")?;
             let idx = match color {
                 "red" => 0,
                 "blue" => 1,
                 "green" => 2,
-                _ => unreachable!(),
+                _ => {
+                    return Err(AocError::parse(
+                        1,
+                        column_of(s, color),
+                        format!("unknown color \"{}\"", color),
+                    ))
+                }
             };
-            rec[idx] = rec[idx].max(val);
-            rec
-        });
+            turn_max[idx] = turn_max[idx].max(val);
+        }
         Ok(Self(turn_max))
     }
 }
@@ -44,30 +49,51 @@ impl Game {
     }
 }
 
-pub fn solution_day_02_01(file_path: String) -> Option<usize> {
-    let result = fs::read_to_string(file_path)
-        .expect("Invalid input file.")
-        .lines()
-        .enumerate()
-        .filter_map(|(idx, l)| {
-            let g: Game = l.parse().unwrap();
-            if g.is_valid(&[12, 14, 13]) {
-                Some(idx + 1)
-            } else {
-                None
-            }
-        })
-        .sum();
-    Some(result)
+pub struct Day02;
+
+impl Solution for Day02 {
+    const DAY: u8 = 2;
+    type Input = Vec<Game>;
+    type Output = usize;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse_lines(input, str::parse)
+    }
+
+    fn part1(input: &Self::Input) -> Result<usize> {
+        let result = input
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, g)| {
+                if g.is_valid(&[12, 14, 13]) {
+                    Some(idx + 1)
+                } else {
+                    None
+                }
+            })
+            .sum();
+        Ok(result)
+    }
+
+    fn part2(input: &Self::Input) -> Result<usize> {
+        Ok(input.iter().map(|g| g.power()).sum())
+    }
+}
+
+pub fn solution_day_02_01(file_path: String) -> Result<usize> {
+    solution_day_02_01_from_str(&read_input(file_path)?)
+}
+
+pub fn solution_day_02_01_from_str(input: &str) -> Result<usize> {
+    Day02::solve_part1(input)
 }
 
-pub fn solution_day_02_02(file_path: String) -> Option<usize> {
-    let result = fs::read_to_string(file_path)
-        .expect("Invalid input file.")
-        .lines()
-        .map(|l| l.parse::<Game>().unwrap().power())
-        .sum();
-    Some(result)
+pub fn solution_day_02_02(file_path: String) -> Result<usize> {
+    solution_day_02_02_from_str(&read_input(file_path)?)
+}
+
+pub fn solution_day_02_02_from_str(input: &str) -> Result<usize> {
+    Day02::solve_part2(input)
 }
 
 #[cfg(test)]
@@ -88,6 +114,15 @@ mod tests {
         assert_eq!(result, 2286);
     }
 
+    #[test]
+    fn test_day_02_invalid_input() {
+        let result = solution_day_02_01_from_str("Game 1: 3 blue, 4 red\nGame 2: 1 purple");
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "Day 02, line 2, column 11: unknown color \"purple\""
+        );
+    }
+
     #[test]
     #[ignore]
     fn output_day_02_01() {
@@ -1,4 +1,6 @@
-use std::fs;
+use super::{read_input, Solution};
+use crate::error::{AocError, Result};
+use crate::utils::collections::Faux2DArray;
 
 // Advent of Code 2023 - Day 03
 #[derive(Debug)]
@@ -17,91 +19,94 @@ struct Symbol {
 }
 
 #[derive(Debug)]
-struct Schema {
+pub struct Schema {
+    grid: Faux2DArray<char>,
     parts: Vec<PartIndex>,
     symbols: Vec<Symbol>,
 }
 
-#[derive(Debug, PartialEq, Eq)]
-struct ParseSchemaError;
+fn parse_part_number(digits: &[char], y: usize, x: usize) -> Result<usize> {
+    digits
+        .iter()
+        .collect::<String>()
+        .parse::<usize>()
+        .map_err(|_| AocError::parse(y + 1, x + 1, "part number is too large"))
+}
 
 impl std::str::FromStr for Schema {
-    type Err = ParseSchemaError;
+    type Err = AocError;
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
+    fn from_str(s: &str) -> Result<Self> {
         let mut parsed = Self {
+            grid: s.parse()?,
             parts: vec![],
             symbols: vec![],
         };
-        let width = s.split_once('\n').unwrap().0.len();
+        let width = parsed.grid.width;
         let mut part_nums = vec![];
-        for (y, row_str) in s.lines().enumerate() {
-            for (x, v) in row_str.chars().enumerate() {
-                if v.is_ascii_digit() {
-                    part_nums.push(v);
-                } else {
-                    if !part_nums.is_empty() {
-                        parsed.parts.push(PartIndex {
-                            num: part_nums
-                                .iter()
-                                .collect::<String>()
-                                .parse::<usize>()
-                                .unwrap(),
-                            y,
-                            x_start: x - part_nums.len(),
-                            x_end: x - 1,
-                        });
-                        part_nums = vec![];
-                    }
-                    if v != '.' {
-                        parsed.symbols.push(Symbol { symbol: v, x, y })
-                    }
-                }
+        for ((x, y), &v) in parsed.grid.positions() {
+            // A number never runs on into the next row.
+            if x == 0 && y > 0 {
+                parsed
+                    .parts
+                    .extend(take_part(&mut part_nums, y - 1, width)?);
+            }
+            if v.is_ascii_digit() {
+                part_nums.push(v);
+                continue;
             }
-            if !part_nums.is_empty() {
-                parsed.parts.push(PartIndex {
-                    num: part_nums
-                        .iter()
-                        .collect::<String>()
-                        .parse::<usize>()
-                        .unwrap(),
-                    y,
-                    x_start: width - part_nums.len() - 1,
-                    x_end: width - 1,
-                });
-                part_nums = vec![];
+            parsed.parts.extend(take_part(&mut part_nums, y, x)?);
+            if v != '.' {
+                parsed.symbols.push(Symbol { symbol: v, x, y })
             }
         }
+        let last_row = parsed.grid.height().saturating_sub(1);
+        parsed
+            .parts
+            .extend(take_part(&mut part_nums, last_row, width)?);
 
         Ok(parsed)
     }
 }
 
+// The number whose digits end just before `end` on row `y`, if any.
+fn take_part(part_nums: &mut Vec<char>, y: usize, end: usize) -> Result<Option<PartIndex>> {
+    if part_nums.is_empty() {
+        return Ok(None);
+    }
+    let x_start = end - part_nums.len();
+    let part = PartIndex {
+        num: parse_part_number(part_nums, y, x_start)?,
+        y,
+        x_start,
+        x_end: end - 1,
+    };
+    part_nums.clear();
+    Ok(Some(part))
+}
+
 impl Schema {
-    pub fn find_adjacent(&self, sym: &Symbol) -> Vec<&PartIndex> {
-        let min_row_limit = if sym.y == 0 { 0 } else { sym.y - 1 };
-        let min_col_limit = if sym.x == 0 { 0 } else { sym.x - 1 };
+    fn find_adjacent(&self, sym: &Symbol) -> Vec<&PartIndex> {
+        let around: Vec<(usize, usize)> = self
+            .grid
+            .neighbors8(sym.x, sym.y)
+            .map(|(pos, _)| pos)
+            .collect();
         self.parts
             .iter()
             .filter(|p| {
-                if p.y > sym.y + 1 || p.y < min_row_limit {
-                    return false;
-                }
-                for x in p.x_start..=p.x_end {
-                    if x <= sym.x + 1 && x >= min_col_limit {
-                        return true;
-                    }
-                }
-                false
+                around
+                    .iter()
+                    .any(|&(x, y)| y == p.y && (p.x_start..=p.x_end).contains(&x))
             })
             .collect()
     }
 
-    pub fn find_all_valid_parts(&self) -> impl Iterator<Item = &PartIndex> {
+    fn find_all_valid_parts(&self) -> impl Iterator<Item = &PartIndex> {
         self.symbols.iter().flat_map(|sym| self.find_adjacent(sym))
     }
 
-    pub fn get_gear_ratio(&self, sym: &Symbol) -> Option<usize> {
+    fn get_gear_ratio(&self, sym: &Symbol) -> Option<usize> {
         if sym.symbol != '*' {
             return None;
         }
@@ -113,26 +118,46 @@ impl Schema {
     }
 }
 
-pub fn solution_day_03_01(file_path: String) -> Option<usize> {
-    let schema: Schema = fs::read_to_string(file_path)
-        .expect("Invalid File.")
-        .parse()
-        .unwrap();
-    let res = schema.find_all_valid_parts().map(|p| p.num).sum();
-    Some(res)
+pub struct Day03;
+
+impl Solution for Day03 {
+    const DAY: u8 = 3;
+    type Input = Schema;
+    type Output = usize;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        input.parse()
+    }
+
+    fn part1(schema: &Self::Input) -> Result<usize> {
+        let res = schema.find_all_valid_parts().map(|p| p.num).sum();
+        Ok(res)
+    }
+
+    fn part2(schema: &Self::Input) -> Result<usize> {
+        let res = schema
+            .symbols
+            .iter()
+            .filter_map(|s| schema.get_gear_ratio(s))
+            .sum();
+        Ok(res)
+    }
 }
 
-pub fn solution_day_03_02(file_path: String) -> Option<usize> {
-    let schema: Schema = fs::read_to_string(file_path)
-        .expect("Invalid File.")
-        .parse()
-        .unwrap();
-    let res = schema
-        .symbols
-        .iter()
-        .filter_map(|s| schema.get_gear_ratio(s))
-        .sum();
-    Some(res)
+pub fn solution_day_03_01(file_path: String) -> Result<usize> {
+    solution_day_03_01_from_str(&read_input(file_path)?)
+}
+
+pub fn solution_day_03_01_from_str(input: &str) -> Result<usize> {
+    Day03::solve_part1(input)
+}
+
+pub fn solution_day_03_02(file_path: String) -> Result<usize> {
+    solution_day_03_02_from_str(&read_input(file_path)?)
+}
+
+pub fn solution_day_03_02_from_str(input: &str) -> Result<usize> {
+    Day03::solve_part2(input)
 }
 
 #[cfg(test)]
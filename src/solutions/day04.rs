@@ -1,18 +1,19 @@
-use std::fs;
+use super::{read_input, Solution};
+use crate::error::{parse_lines, parse_token, split_token, AocError, Result};
 
 // Advent of Code 2023 - Day 04
-fn win_count(inp: &str) -> usize {
-    let (_, numbers) = inp.split_once(':').unwrap();
-    let (winning, available) = numbers.split_once(" | ").unwrap();
+fn win_count(inp: &str) -> Result<usize> {
+    let (_, numbers) = split_token(inp, inp, ":")?;
+    let (winning, available) = split_token(inp, numbers, " | ")?;
     let winning: Vec<usize> = winning
         .split_whitespace()
-        .map(|n| n.trim().parse().unwrap())
-        .collect();
-    available
+        .map(|n| parse_token(inp, n, "number"))
+        .collect::<Result<_>>()?;
+    let available: Vec<usize> = available
         .split_whitespace()
-        .map(|n| n.trim().parse().unwrap())
-        .filter(|a| winning.contains(a))
-        .count()
+        .map(|n| parse_token(inp, n, "number"))
+        .collect::<Result<_>>()?;
+    Ok(available.iter().filter(|a| winning.contains(a)).count())
 }
 
 pub fn points(count: usize) -> usize {
@@ -22,35 +23,58 @@ pub fn points(count: usize) -> usize {
     2_usize.pow((count - 1).try_into().unwrap())
 }
 
-fn total_won(cards: &Vec<usize>) -> usize {
+fn total_won(cards: &[usize]) -> Result<usize> {
     let mut count_cache = vec![1; cards.len()];
     for (index, card_win) in cards.iter().enumerate() {
+        if index + card_win >= cards.len() {
+            return Err(AocError::solve(format!(
+                "card {} wins copies of cards past the end of the table",
+                index + 1
+            )));
+        }
         if card_win > &0 {
             for x in index + 1..=(index + card_win) {
                 count_cache[x] += count_cache[index]
             }
         }
     }
-    count_cache.iter().sum()
+    Ok(count_cache.iter().sum())
+}
+
+pub struct Day04;
+
+impl Solution for Day04 {
+    const DAY: u8 = 4;
+    type Input = Vec<usize>;
+    type Output = usize;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse_lines(input, win_count)
+    }
+
+    fn part1(cards: &Self::Input) -> Result<usize> {
+        Ok(cards.iter().map(|c| points(*c)).sum())
+    }
+
+    fn part2(cards: &Self::Input) -> Result<usize> {
+        total_won(cards)
+    }
+}
+
+pub fn solution_day_04_01(file_path: String) -> Result<usize> {
+    solution_day_04_01_from_str(&read_input(file_path)?)
+}
+
+pub fn solution_day_04_01_from_str(input: &str) -> Result<usize> {
+    Day04::solve_part1(input)
 }
 
-pub fn solution_day_04_01(file_path: String) -> Option<usize> {
-    Some(
-        fs::read_to_string(file_path)
-            .expect("Invalid File")
-            .lines()
-            .map(|l| points(win_count(l)))
-            .sum(),
-    )
+pub fn solution_day_04_02(file_path: String) -> Result<usize> {
+    solution_day_04_02_from_str(&read_input(file_path)?)
 }
 
-pub fn solution_day_04_02(file_path: String) -> Option<usize> {
-    let cards: Vec<usize> = fs::read_to_string(file_path)
-        .expect("Invalid File")
-        .lines()
-        .map(win_count)
-        .collect();
-    Some(total_won(&cards))
+pub fn solution_day_04_02_from_str(input: &str) -> Result<usize> {
+    Day04::solve_part2(input)
 }
 
 #[cfg(test)]
@@ -1,4 +1,5 @@
-use std::fs;
+use super::{read_input, Solution};
+use crate::error::{parse_lines, parse_token, sections, split_token, AocError, Result};
 
 // Advent of Code 2023 - Day 05
 
@@ -12,27 +13,39 @@ struct AlmanacRange {
 
 impl AlmanacRange {
     fn includes(&self, val: usize) -> bool {
-        val >= self.start && val < self.start + self.length
+        val >= self.start && val - self.start < self.length
     }
 
+    // Ranges reaching past the largest number stop there.
     fn end(&self) -> usize {
-        self.start + self.length
+        self.start.saturating_add(self.length)
+    }
+
+    // Where `val` goes, for a value the rule includes.
+    fn translate(&self, val: usize) -> Result<usize> {
+        self.dest
+            .unwrap_or(self.start)
+            .checked_add(val - self.start)
+            .ok_or_else(|| AocError::solve(format!("{} is mapped past the largest number", val)))
     }
 }
 
 trait AlmanacRangeVec {
     fn merge(&self) -> Vec<AlmanacRange>;
-    fn generate_ranges(&self, range: &AlmanacRange) -> Vec<AlmanacRange>;
+    fn generate_ranges(&self, range: &AlmanacRange) -> Result<Vec<AlmanacRange>>;
 }
 
 impl AlmanacRangeVec for Vec<AlmanacRange> {
     fn merge(&self) -> Vec<AlmanacRange> {
         let mut merged = vec![];
+        if self.is_empty() {
+            return merged;
+        }
         let mut current = self[0].clone();
         for r in self {
             if current.includes(r.start) {
-                current.length = current.length.max(r.length + r.start - current.start);
-            } else if r.start == current.start + current.length {
+                current.length = current.length.max(r.end() - current.start);
+            } else if r.start == current.end() {
                 current.length += r.length;
             } else {
                 merged.push(current.clone());
@@ -43,7 +56,7 @@ impl AlmanacRangeVec for Vec<AlmanacRange> {
         merged
     }
 
-    fn generate_ranges(&self, range: &AlmanacRange) -> Vec<AlmanacRange> {
+    fn generate_ranges(&self, range: &AlmanacRange) -> Result<Vec<AlmanacRange>> {
         let mut results = vec![];
         let mut cursor = range.start;
         let mut remaining = range.length;
@@ -74,7 +87,7 @@ impl AlmanacRangeVec for Vec<AlmanacRange> {
             };
             if prev.includes(cursor) {
                 results.push(AlmanacRange {
-                    start: prev.dest.unwrap() + cursor - prev.start,
+                    start: prev.translate(cursor)?,
                     length: remaining.min(prev.end() - cursor),
                     dest: None,
                 });
@@ -90,126 +103,156 @@ impl AlmanacRangeVec for Vec<AlmanacRange> {
             cursor = next.start;
             index += 1;
         }
-        results
+        Ok(results)
     }
 }
 
-struct Almanac {
+pub struct Almanac {
     seeds: Vec<usize>,
     rules: Vec<Vec<AlmanacRange>>,
 }
 
-#[derive(Debug, PartialEq, Eq)]
-struct ParseAlmanacError;
-
 impl std::str::FromStr for Almanac {
-    type Err = ParseAlmanacError;
+    type Err = AocError;
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
+    fn from_str(s: &str) -> Result<Self> {
         let mut rval = Self {
             seeds: vec![],
             rules: vec![],
         };
-        let mut sections = s.split("\n\n");
-        let (_, seeds) = sections.next().unwrap().split_once(": ").unwrap();
+        let mut sections = sections(s);
+        let seed_line = sections.next().map(|(_, l)| l).unwrap_or_default();
+        let (_, seeds) = split_token(seed_line, seed_line, ": ")?;
         for i in seeds.split(' ') {
-            let v = i.parse::<usize>().unwrap();
-            rval.seeds.push(v);
+            rval.seeds.push(parse_token(seed_line, i, "seed")?);
         }
-        rval.rules = sections.map(Almanac::parse_section).collect();
+        rval.rules = sections
+            .map(|(offset, section)| Almanac::parse_section(section).map_err(|e| e.below(offset)))
+            .collect::<Result<_>>()?;
         Ok(rval)
     }
 }
 
 impl Almanac {
-    fn seeds_as_ranges(&self) -> Vec<AlmanacRange> {
-        self.seeds
+    fn seeds_as_ranges(&self) -> Result<Vec<AlmanacRange>> {
+        if !self.seeds.len().is_multiple_of(2) {
+            return Err(AocError::solve("seeds do not come in pairs"));
+        }
+        Ok(self
+            .seeds
             .chunks(2)
             .map(|pair| AlmanacRange {
                 start: pair[0],
                 length: pair[1],
                 dest: None,
             })
-            .collect()
-    }
-
-    fn parse_section(inp: &str) -> Vec<AlmanacRange> {
-        let mut res: Vec<AlmanacRange> = inp
-            .lines()
-            .skip(1)
-            .map(|l| {
-                let mut parts = l.splitn(3, ' ');
-                let dest = parts.next().unwrap().parse::<usize>().unwrap();
-                let src = parts.next().unwrap().parse::<usize>().unwrap();
-                let count = parts.next().unwrap().parse::<usize>().unwrap();
-                AlmanacRange {
-                    start: src,
-                    length: count,
-                    dest: Some(dest),
-                }
+            .collect())
+    }
+
+    fn parse_section(inp: &str) -> Result<Vec<AlmanacRange>> {
+        let (_, rules) = inp.split_once('\n').unwrap_or((inp, ""));
+        let mut res: Vec<AlmanacRange> = parse_lines(rules, |l| {
+            let mut parts = l.splitn(3, ' ');
+            let mut next = |what| match parts.next() {
+                Some(v) => parse_token::<usize>(l, v, what),
+                None => Err(AocError::parse(1, l.len() + 1, format!("missing {}", what))),
+            };
+            let dest = next("destination")?;
+            let src = next("source")?;
+            let count = next("length")?;
+            Ok(AlmanacRange {
+                start: src,
+                length: count,
+                dest: Some(dest),
             })
-            .collect();
-        res.sort_by(|a, b| a.start.cmp(&b.start));
-        res
+        })
+        .map_err(|e| e.below(1))?;
+        res.sort_by_key(|a| a.start);
+        Ok(res)
     }
 
-    fn find_location(&self, init: usize) -> usize {
+    fn find_location(&self, init: usize) -> Result<usize> {
         let mut current = init;
         for section in self.rules.iter() {
-            for rule in section {
-                if rule.includes(current) {
-                    current = rule.dest.unwrap() + current - rule.start;
-                    break;
-                }
+            if let Some(rule) = section.iter().find(|rule| rule.includes(current)) {
+                current = rule.translate(current)?;
             }
         }
-        current
+        Ok(current)
     }
 }
 
-pub fn solution_day_05_01(file_path: String) -> Option<usize> {
-    let almanac: Almanac = fs::read_to_string(file_path)
-        .expect("Invalid Input file.")
-        .parse()
-        .unwrap();
-
-    let res = almanac
-        .seeds
-        .iter()
-        .map(|seed| almanac.find_location(*seed))
-        .min();
-    res
+pub struct Day05;
+
+impl Solution for Day05 {
+    const DAY: u8 = 5;
+    type Input = Almanac;
+    type Output = usize;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        input.parse()
+    }
+
+    fn part1(almanac: &Self::Input) -> Result<usize> {
+        almanac
+            .seeds
+            .iter()
+            .map(|seed| almanac.find_location(*seed))
+            .collect::<Result<Vec<usize>>>()?
+            .into_iter()
+            .min()
+            .ok_or_else(|| AocError::solve("no seeds to plant"))
+    }
+
+    fn part2(almanac: &Self::Input) -> Result<usize> {
+        let seed_ranges = almanac.seeds_as_ranges()?;
+        let res = almanac
+            .rules
+            .iter()
+            .try_fold(seed_ranges, |ranges, section| {
+                let mut vals = vec![];
+                for sr in &ranges {
+                    vals.extend(section.generate_ranges(sr)?);
+                }
+                vals.retain(|r| r.length > 0);
+                vals.sort_by_key(|a| a.start);
+                Ok::<_, AocError>(vals.merge())
+            })?
+            .iter()
+            .min()
+            .ok_or_else(|| AocError::solve("no seeds to plant"))?
+            .start;
+        Ok(res)
+    }
 }
 
-pub fn solution_day_05_02(file_path: String) -> Option<usize> {
-    let almanac: Almanac = fs::read_to_string(file_path)
-        .expect("Invalid Input file.")
-        .parse()
-        .unwrap();
-    let seed_ranges = almanac.seeds_as_ranges();
-    let res = almanac
-        .rules
-        .iter()
-        .fold(seed_ranges, |ranges, section| {
-            let mut vals: Vec<AlmanacRange> = ranges
-                .iter()
-                .flat_map(|sr| section.generate_ranges(sr))
-                .filter(|r| r.length > 0)
-                .collect();
-            vals.sort_by(|a, b| a.start.cmp(&b.start));
-            vals.merge()
-        })
-        .iter()
-        .min()
-        .unwrap()
-        .start;
-    Some(res)
+pub fn solution_day_05_01(file_path: String) -> Result<usize> {
+    solution_day_05_01_from_str(&read_input(file_path)?)
+}
+
+pub fn solution_day_05_01_from_str(input: &str) -> Result<usize> {
+    Day05::solve_part1(input)
+}
+
+pub fn solution_day_05_02(file_path: String) -> Result<usize> {
+    solution_day_05_02_from_str(&read_input(file_path)?)
+}
+
+pub fn solution_day_05_02_from_str(input: &str) -> Result<usize> {
+    Day05::solve_part2(input)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_overflow() {
+        let input = "seeds: 11 5\n\na-to-b map:\n18446744073709551615 10 5";
+        assert!(solution_day_05_01_from_str(input).is_err());
+        assert!(solution_day_05_02_from_str(input).is_err());
+    }
+
     #[test]
     fn test_day_05_01() {
         let file_path: String = String::from("src/inputs/day05e.txt");
@@ -1,7 +1,9 @@
-use std::fs;
+use super::{read_input, Solution};
+use crate::error::{parse_token, split_token, AocError, Result};
 
 // Advent of Code 2023 - Day 06
 
+// Zero when the record can't be beaten, the roots then being missing or too close together.
 fn winning_count(stat: &(f64, f64)) -> usize {
     let (b, c) = *stat;
     let delta = (b.powi(2) - (4.0 * c)).sqrt();
@@ -13,52 +15,107 @@ fn winning_count(stat: &(f64, f64)) -> usize {
     if end * (b - end) > c {
         end += 1.0
     }
-    (end - start) as usize - 1
+    let count = end - start - 1.0;
+    if count.is_nan() || count < 1.0 {
+        return 0;
+    }
+    count as usize
 }
 
-fn parse_line(l: &str) -> impl Iterator<Item = f64> + '_ {
-    l.split_once(':')
-        .unwrap()
-        .1
+// The races on a line, and the single race you get when ignoring the spaces.
+fn parse_line(l: &str, label: &str) -> Result<(Vec<f64>, f64)> {
+    let (name, values) = split_token(l, l, ":")?;
+    if name != label {
+        return Err(AocError::parse(
+            1,
+            1,
+            format!("expected \"{}:\", got \"{}:\"", label, name),
+        ));
+    }
+    let separate = values
         .split_whitespace()
-        .map(|s| s.parse::<f64>().unwrap())
+        .map(|v| parse_token::<f64>(l, v, "number"))
+        .collect::<Result<Vec<f64>>>()?;
+    if separate.is_empty() {
+        return Err(AocError::parse(1, l.len() + 1, "expected numbers"));
+    }
+    let combined = parse_token(l, &values.replace(' ', ""), "number")?;
+    Ok((separate, combined))
 }
 
-fn parse(data: &str) -> Vec<(f64, f64)> {
-    let (times, distances) = data.split_once('\n').unwrap();
-    parse_line(times).zip(parse_line(distances)).collect()
+pub struct Day06;
+
+impl Solution for Day06 {
+    const DAY: u8 = 6;
+    // The races as written, and the single race you get when ignoring the spaces.
+    type Input = (Vec<(f64, f64)>, (f64, f64));
+    type Output = usize;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        let mut lines = input.lines();
+        let (times, time) = parse_line(lines.next().unwrap_or_default(), "Time")?;
+        let distance_line = lines.next().unwrap_or_default();
+        let (distances, distance) =
+            parse_line(distance_line, "Distance").map_err(|e| e.below(1))?;
+        if times.len() != distances.len() {
+            return Err(AocError::parse(
+                2,
+                distance_line.len() + 1,
+                format!(
+                    "expected {} distances, got {}",
+                    times.len(),
+                    distances.len()
+                ),
+            ));
+        }
+        Ok((times.into_iter().zip(distances).collect(), (time, distance)))
+    }
+
+    fn part1(input: &Self::Input) -> Result<usize> {
+        input
+            .0
+            .iter()
+            .try_fold(1usize, |acc, s| acc.checked_mul(winning_count(s)))
+            .ok_or_else(|| AocError::solve("the product of the ways to win is too large"))
+    }
+
+    fn part2(input: &Self::Input) -> Result<usize> {
+        Ok(winning_count(&input.1))
+    }
 }
 
-fn parse_combined(data: &str) -> (f64, f64) {
-    let mut parts = data.split('\n').map(|v| {
-        v.replace(' ', "")
-            .split_once(':')
-            .unwrap()
-            .1
-            .parse::<f64>()
-            .unwrap()
-    });
-    (parts.next().unwrap(), parts.next().unwrap())
+pub fn solution_day_06_01(file_path: String) -> Result<usize> {
+    solution_day_06_01_from_str(&read_input(file_path)?)
 }
 
-pub fn solution_day_06_01(file_path: String) -> Option<usize> {
-    Some(
-        parse(&fs::read_to_string(file_path).expect("Invalid Input."))
-            .iter()
-            .fold(1, |acc, s| acc * winning_count(s)),
-    )
+pub fn solution_day_06_01_from_str(input: &str) -> Result<usize> {
+    Day06::solve_part1(input)
+}
+
+pub fn solution_day_06_02(file_path: String) -> Result<usize> {
+    solution_day_06_02_from_str(&read_input(file_path)?)
 }
 
-pub fn solution_day_06_02(file_path: String) -> Option<usize> {
-    Some(winning_count(&parse_combined(
-        &fs::read_to_string(file_path).expect("Invalid Input."),
-    )))
+pub fn solution_day_06_02_from_str(input: &str) -> Result<usize> {
+    Day06::solve_part2(input)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_unbeatable_and_labels() {
+        assert_eq!(winning_count(&(3.0, 10.0)), 0);
+        assert_eq!(winning_count(&(4.0, 4.0)), 0);
+        assert_eq!(winning_count(&(7.0, 9.0)), 4);
+        assert_eq!(solution_day_06_01_from_str("Time: 3\nDistance: 10"), Ok(0));
+        assert_eq!(
+            solution_day_06_01_from_str("Time: 7\nDistanc: 9").unwrap_err(),
+            AocError::parse(2, 1, "expected \"Distance:\", got \"Distanc:\"").in_day(6)
+        );
+    }
+
     #[test]
     fn test_day_06_01() {
         let file_path: String = String::from("src/inputs/day06e.txt");
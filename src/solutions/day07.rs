@@ -1,5 +1,8 @@
 use rayon::prelude::*;
-use std::{collections::HashMap, fs};
+use std::collections::HashMap;
+
+use super::{read_input, Solution};
+use crate::error::{parse_lines, parse_token, split_token, AocError, Result};
 // Advent of Code 2023 - Day 07
 
 #[derive(PartialEq, Eq, Ord, PartialOrd)]
@@ -38,10 +41,9 @@ impl Hand {
         }
     }
 
-    fn from_str(val: &str, wild: bool) -> Self {
+    fn new(hand: &str, bid: usize, wild: bool) -> Self {
         let j = if wild { '1' } else { 'U' };
-        let (h, b) = val.split_once(' ').unwrap();
-        let cards: String = h
+        let cards: String = hand
             .chars()
             .map(|c| match c {
                 'J' => j,
@@ -52,35 +54,67 @@ impl Hand {
             })
             .collect();
         let value = Hand::get_value(&cards);
-        Self {
-            value,
-            cards,
-            bid: b.trim().parse().unwrap(),
-        }
+        Self { value, cards, bid }
     }
 }
 
-pub fn get_total(file_path: String, wild: bool) -> Option<usize> {
-    let mut hands: Vec<Hand> = fs::read_to_string(file_path)
-        .expect("Invalid Input File.")
-        .par_lines()
-        .map(|l| Hand::from_str(l, wild))
+fn get_total(input: &[(String, usize)], wild: bool) -> usize {
+    let mut hands: Vec<Hand> = input
+        .par_iter()
+        .map(|(hand, bid)| Hand::new(hand, *bid, wild))
         .collect();
     hands.sort();
-    Some(
-        hands
-            .iter()
-            .enumerate()
-            .fold(0, |total, (idx, hand)| total + (hand.bid * (idx + 1))),
-    )
+    hands
+        .iter()
+        .enumerate()
+        .fold(0, |total, (idx, hand)| total + (hand.bid * (idx + 1)))
+}
+
+fn parse_hand(l: &str) -> Result<(String, usize)> {
+    let (h, b) = split_token(l, l, " ")?;
+    if let Some(idx) = h.find(|c| !"23456789TJQKA".contains(c)) {
+        return Err(AocError::parse(1, idx + 1, "invalid card"));
+    }
+    if h.len() != 5 {
+        return Err(AocError::parse(1, 1, "a hand has five cards"));
+    }
+    Ok((h.to_string(), parse_token(l, b.trim(), "bid")?))
+}
+
+pub struct Day07;
+
+impl Solution for Day07 {
+    const DAY: u8 = 7;
+    type Input = Vec<(String, usize)>;
+    type Output = usize;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse_lines(input, parse_hand)
+    }
+
+    fn part1(input: &Self::Input) -> Result<usize> {
+        Ok(get_total(input, false))
+    }
+
+    fn part2(input: &Self::Input) -> Result<usize> {
+        Ok(get_total(input, true))
+    }
+}
+
+pub fn solution_day_07_01(file_path: String) -> Result<usize> {
+    solution_day_07_01_from_str(&read_input(file_path)?)
+}
+
+pub fn solution_day_07_01_from_str(input: &str) -> Result<usize> {
+    Day07::solve_part1(input)
 }
 
-pub fn solution_day_07_01(file_path: String) -> Option<usize> {
-    get_total(file_path, false)
+pub fn solution_day_07_02(file_path: String) -> Result<usize> {
+    solution_day_07_02_from_str(&read_input(file_path)?)
 }
 
-pub fn solution_day_07_02(file_path: String) -> Option<usize> {
-    get_total(file_path, true)
+pub fn solution_day_07_02_from_str(input: &str) -> Result<usize> {
+    Day07::solve_part2(input)
 }
 
 #[cfg(test)]
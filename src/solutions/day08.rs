@@ -1,6 +1,9 @@
 use num::integer::lcm;
 use rayon::prelude::*;
-use std::{collections::HashMap, fs};
+use std::collections::{HashMap, HashSet};
+
+use super::{read_input, Solution};
+use crate::error::{column_of, parse_lines, split_token, AocError, Result};
 // Advent of Code 2023 - Day 08
 
 #[derive(Debug)]
@@ -9,12 +12,14 @@ enum Direction {
     Right,
 }
 
-impl std::convert::From<char> for Direction {
-    fn from(value: char) -> Self {
+impl std::convert::TryFrom<char> for Direction {
+    type Error = char;
+
+    fn try_from(value: char) -> std::result::Result<Self, Self::Error> {
         match value {
-            'L' => Self::Left,
-            'R' => Self::Right,
-            _ => unreachable!(),
+            'L' => Ok(Self::Left),
+            'R' => Ok(Self::Right),
+            c => Err(c),
         }
     }
 }
@@ -26,18 +31,16 @@ struct Node {
     right: String,
 }
 
-#[derive(PartialEq, Eq, Debug)]
-struct ParseNodeError;
-
 impl std::str::FromStr for Node {
-    type Err = ParseNodeError;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (name, pair) = s.split_once(" = ").unwrap();
-        let mut pair = pair.to_string();
-        pair.pop();
-        pair.remove(0);
-        let (left, right) = pair.split_once(", ").unwrap();
+    type Err = AocError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let (name, pair) = split_token(s, s, " = ")?;
+        let pair = pair
+            .strip_prefix('(')
+            .and_then(|p| p.strip_suffix(')'))
+            .ok_or_else(|| AocError::parse(1, column_of(s, pair), "expected \"(left, right)\""))?;
+        let (left, right) = split_token(s, pair, ", ")?;
         Ok(Self {
             name: name.to_string(),
             left: left.to_string(),
@@ -47,25 +50,48 @@ impl std::str::FromStr for Node {
 }
 
 #[derive(Debug)]
-struct Map {
+pub struct Map {
     instructions: Vec<Direction>,
     nodes: HashMap<String, Node>,
 }
 
-#[derive(PartialEq, Eq, Debug)]
-struct ParseMapError;
-
 impl std::str::FromStr for Map {
-    type Err = ParseMapError;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (ins, node_strings) = s.split_once("\n\n").unwrap();
-        let instructions: Vec<Direction> = ins.chars().map(|c| c.into()).collect();
-        let mut nodes = HashMap::new();
-        for l in node_strings.lines() {
-            let node: Node = l.parse().unwrap();
-            nodes.insert(node.name.clone(), node);
+    type Err = AocError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let (ins, node_strings) = s
+            .split_once("\n\n")
+            .ok_or_else(|| AocError::parse(2, 1, "expected a blank line after the instructions"))?;
+        let instructions = ins
+            .chars()
+            .enumerate()
+            .map(|(idx, c)| {
+                Direction::try_from(c)
+                    .map_err(|c| AocError::parse(1, idx + 1, format!("invalid direction '{}'", c)))
+            })
+            .collect::<Result<Vec<Direction>>>()?;
+        if instructions.is_empty() {
+            return Err(AocError::parse(1, 1, "no instructions"));
+        }
+        let node_list = parse_lines(node_strings, str::parse::<Node>).map_err(|e| e.below(2))?;
+        let names: HashSet<&str> = node_list.iter().map(|n| n.name.as_str()).collect();
+        // Walking trusts that every node it reaches exists.
+        for (idx, (node, l)) in node_list.iter().zip(node_strings.lines()).enumerate() {
+            for target in [&node.left, &node.right] {
+                if !names.contains(target.as_str()) {
+                    let column = l.rfind(target.as_str()).map_or(1, |c| c + 1);
+                    return Err(AocError::parse(
+                        idx + 3,
+                        column,
+                        format!("unknown node \"{}\"", target),
+                    ));
+                }
+            }
         }
+        let nodes = node_list
+            .into_iter()
+            .map(|node| (node.name.clone(), node))
+            .collect();
         Ok(Self {
             instructions,
             nodes,
@@ -90,11 +116,14 @@ impl Map {
         steps
     }
 
-    fn traverse(&self) -> usize {
-        self.walk("AAA", |x| x == "ZZZ")
+    fn traverse(&self) -> Result<usize> {
+        if !self.nodes.contains_key("AAA") {
+            return Err(AocError::solve("there is no node \"AAA\" to start from"));
+        }
+        Ok(self.walk("AAA", |x| x == "ZZZ"))
     }
 
-    fn traverse_like_a_ghost(&self) -> usize {
+    fn traverse_like_a_ghost(&self) -> Result<usize> {
         self.nodes
             .keys()
             .clone()
@@ -103,24 +132,44 @@ impl Map {
             .into_par_iter()
             .map(|v| self.walk(v, |x| x.ends_with('Z')))
             .reduce_with(lcm)
-            .unwrap()
+            .ok_or_else(|| AocError::solve("there are no nodes ending with 'A' to start from"))
     }
 }
 
-pub fn solution_day_08_01(file_path: String) -> Option<usize> {
-    let map: Map = fs::read_to_string(file_path)
-        .expect("Invalid input.")
-        .parse()
-        .unwrap();
-    Some(map.traverse())
+pub struct Day08;
+
+impl Solution for Day08 {
+    const DAY: u8 = 8;
+    type Input = Map;
+    type Output = usize;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        input.parse()
+    }
+
+    fn part1(map: &Self::Input) -> Result<usize> {
+        map.traverse()
+    }
+
+    fn part2(map: &Self::Input) -> Result<usize> {
+        map.traverse_like_a_ghost()
+    }
+}
+
+pub fn solution_day_08_01(file_path: String) -> Result<usize> {
+    solution_day_08_01_from_str(&read_input(file_path)?)
+}
+
+pub fn solution_day_08_01_from_str(input: &str) -> Result<usize> {
+    Day08::solve_part1(input)
+}
+
+pub fn solution_day_08_02(file_path: String) -> Result<usize> {
+    solution_day_08_02_from_str(&read_input(file_path)?)
 }
 
-pub fn solution_day_08_02(file_path: String) -> Option<usize> {
-    let map: Map = fs::read_to_string(file_path)
-        .expect("Invalid input.")
-        .parse()
-        .unwrap();
-    Some(map.traverse_like_a_ghost())
+pub fn solution_day_08_02_from_str(input: &str) -> Result<usize> {
+    Day08::solve_part2(input)
 }
 
 #[cfg(test)]
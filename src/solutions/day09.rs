@@ -1,56 +1,101 @@
 // Advent of Code 2023 - Day 09
 
-use std::fs;
-
-fn parse(input: &str) -> Vec<Vec<isize>> {
-    input
-        .lines()
-        .map(|l| {
-            l.split_whitespace()
-                .map(|v| v.parse::<isize>().unwrap())
-                .collect()
-        })
-        .collect()
+use super::{read_input, Solution};
+use crate::error::{parse_lines, parse_token, AocError, Result};
+
+fn parse(input: &str) -> Result<Vec<Vec<isize>>> {
+    parse_lines(input, |l| {
+        l.split_whitespace()
+            .map(|v| parse_token(l, v, "number"))
+            .collect()
+    })
 }
 
-fn generate_series(data: &[isize]) -> Vec<Vec<isize>> {
+// None when a difference doesn't fit.
+fn generate_series(data: &[isize]) -> Option<Vec<Vec<isize>>> {
     let mut series: Vec<Vec<isize>> = vec![data.to_vec()];
     while series.last().unwrap().iter().any(|x| x != &0) {
         let last = series.last().unwrap();
         let new_series = (0..(last.len() - 1))
-            .map(|i| last[i + 1] - last[i])
-            .collect();
+            .map(|i| last[i + 1].checked_sub(last[i]))
+            .collect::<Option<_>>()?;
         series.push(new_series)
     }
-    series
+    Some(series)
+}
+
+fn extrapolate(
+    series: &[isize],
+    accumulator: fn(isize, &Vec<isize>) -> Option<isize>,
+) -> Option<isize> {
+    generate_series(series)?
+        .iter()
+        .rev()
+        .skip(1)
+        .try_fold(0, accumulator)
+}
+
+fn report(
+    data: &[Vec<isize>],
+    accumulator: fn(isize, &Vec<isize>) -> Option<isize>,
+) -> Result<isize> {
+    let mut total: isize = 0;
+    for (idx, series) in data.iter().enumerate() {
+        total = extrapolate(series, accumulator)
+            .and_then(|v| total.checked_add(v))
+            .ok_or_else(|| AocError::solve(format!("history {} is out of range", idx + 1)))?;
+    }
+    Ok(total)
+}
+
+pub struct Day09;
+
+impl Solution for Day09 {
+    const DAY: u8 = 9;
+    type Input = Vec<Vec<isize>>;
+    type Output = isize;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input) -> Result<isize> {
+        report(input, |acc, v| v.last().unwrap().checked_add(acc))
+    }
+
+    fn part2(input: &Self::Input) -> Result<isize> {
+        report(input, |acc, v| v.first().unwrap().checked_sub(acc))
+    }
 }
 
-fn report(data: &[Vec<isize>], accumulator: fn(isize, &Vec<isize>) -> isize) -> isize {
-    data.iter()
-        .map(|series: &Vec<isize>| {
-            generate_series(series)
-                .iter()
-                .rev()
-                .skip(1)
-                .fold(0, accumulator)
-        })
-        .sum()
+pub fn solution_day_09_01(file_path: String) -> Result<isize> {
+    solution_day_09_01_from_str(&read_input(file_path)?)
 }
 
-pub fn solution_day_09_01(file_path: String) -> Option<isize> {
-    let val = parse(&fs::read_to_string(file_path).expect("Invalid Input File."));
-    Some(report(&val, |acc, v| v.last().unwrap() + acc))
+pub fn solution_day_09_01_from_str(input: &str) -> Result<isize> {
+    Day09::solve_part1(input)
 }
 
-pub fn solution_day_09_02(file_path: String) -> Option<isize> {
-    let val = parse(&fs::read_to_string(file_path).expect("Invalid Input File."));
-    Some(report(&val, |acc, v| v.first().unwrap() - acc))
+pub fn solution_day_09_02(file_path: String) -> Result<isize> {
+    solution_day_09_02_from_str(&read_input(file_path)?)
+}
+
+pub fn solution_day_09_02_from_str(input: &str) -> Result<isize> {
+    Day09::solve_part2(input)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_out_of_range() {
+        let input = "0 3 6 9 12 15\n1 3 6 10 15 21 9000000000000000000 -9000000000000000000";
+        let error = Err(AocError::solve("history 2 is out of range").in_day(9));
+        assert_eq!(solution_day_09_01_from_str(input), error);
+        assert_eq!(solution_day_09_02_from_str(input), error);
+    }
+
     #[test]
     fn test_day_09_01() {
         let file_path: String = String::from("src/inputs/day09e.txt");
@@ -1,58 +1,36 @@
-use std::{
-    collections::{HashMap, HashSet, VecDeque},
-    fs,
-};
+use std::collections::{HashSet, VecDeque};
+
+use super::{read_input, Solution};
+use crate::error::{AocError, Result};
+use crate::utils::collections::Faux2DArray;
 
 // Advent of Code 2023 - Day 10
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 struct Terrain {
     kind: char,
     x: usize,
     y: usize,
 }
 
-impl Terrain {
-    pub fn get_hash_key(&self) -> String {
-        format!("{}x{}", self.x, self.y)
-    }
-}
-
 #[derive(Debug, PartialEq, Eq, Clone)]
-struct GroundMap {
+pub struct GroundMap {
     start: Terrain,
-    terrains: HashMap<String, Terrain>,
-    width: usize,
-    height: usize,
+    tiles: Faux2DArray<char>,
 }
 
-#[derive(Debug, PartialEq, Eq)]
-struct ParseGroundMapError;
-
 impl std::str::FromStr for GroundMap {
-    type Err = ParseGroundMapError;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let height = s.lines().count();
-        let width = s.split_once('\n').unwrap().0.len();
-        let mut terrains = HashMap::new();
-        let mut start = None;
-        for (y, line) in s.lines().enumerate() {
-            for (x, c) in line.chars().enumerate() {
-                if c != '.' {
-                    let terrain = Terrain { kind: c, x, y };
-                    if c == 'S' {
-                        start = Some(terrain.clone());
-                    }
-                    terrains.insert(terrain.get_hash_key(), terrain);
-                }
-            }
-        }
+    type Err = AocError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let tiles = Faux2DArray::from_text(s, |c| "|-LJ7F.S".contains(c).then_some(c))?;
+        let ((x, y), _) = tiles
+            .positions()
+            .find(|(_, c)| **c == 'S')
+            .ok_or_else(|| AocError::parse(1, 1, "no starting tile 'S'"))?;
         Ok(Self {
-            start: start.unwrap(),
-            terrains,
-            width,
-            height,
+            start: Terrain { kind: 'S', x, y },
+            tiles,
         })
     }
 }
@@ -63,64 +41,40 @@ const VALID_LEFT: [char; 3] = ['-', 'F', 'L'];
 const VALID_RIGHT: [char; 3] = ['-', '7', 'J'];
 
 impl GroundMap {
-    fn top(&self, current: &Terrain) -> Option<&Terrain> {
-        if current.y != 0 {
-            if let Some(v) = self
-                .terrains
-                .get(&format!("{:?}x{:?}", current.x, current.y - 1))
-            {
-                if VALID_TOP.contains(&v.kind) {
-                    return Some(v);
-                }
-            }
-        }
-        None
+    // The pipe next to `current` in the direction of `offset`, if it connects back to it.
+    fn connected(
+        &self,
+        current: &Terrain,
+        offset: (isize, isize),
+        valid: &[char],
+    ) -> Option<Terrain> {
+        let (x, y) = (current.x as isize + offset.0, current.y as isize + offset.1);
+        let kind = *self.tiles.get(x, y)?;
+        valid.contains(&kind).then_some(Terrain {
+            kind,
+            x: x as usize,
+            y: y as usize,
+        })
     }
 
-    fn bottom(&self, current: &Terrain) -> Option<&Terrain> {
-        if current.y != self.height - 1 {
-            if let Some(v) = self
-                .terrains
-                .get(&format!("{:?}x{:?}", current.x, current.y + 1))
-            {
-                if VALID_BOTTOM.contains(&v.kind) {
-                    return Some(v);
-                }
-            }
-        }
-        None
+    fn top(&self, current: &Terrain) -> Option<Terrain> {
+        self.connected(current, (0, -1), &VALID_TOP)
     }
 
-    fn left(&self, current: &Terrain) -> Option<&Terrain> {
-        if current.x != 0 {
-            if let Some(v) = self
-                .terrains
-                .get(&format!("{:?}x{:?}", current.x - 1, current.y))
-            {
-                if VALID_LEFT.contains(&v.kind) {
-                    return Some(v);
-                }
-            }
-        }
-        None
+    fn bottom(&self, current: &Terrain) -> Option<Terrain> {
+        self.connected(current, (0, 1), &VALID_BOTTOM)
     }
 
-    fn right(&self, current: &Terrain) -> Option<&Terrain> {
-        if current.x != self.width - 1 {
-            if let Some(v) = self
-                .terrains
-                .get(&format!("{:?}x{:?}", current.x + 1, current.y))
-            {
-                if VALID_RIGHT.contains(&v.kind) {
-                    return Some(v);
-                }
-            }
-        }
-        None
+    fn left(&self, current: &Terrain) -> Option<Terrain> {
+        self.connected(current, (-1, 0), &VALID_LEFT)
     }
 
-    fn get_actual_start_type(&self) -> Terrain {
-        let start = self.start.clone();
+    fn right(&self, current: &Terrain) -> Option<Terrain> {
+        self.connected(current, (1, 0), &VALID_RIGHT)
+    }
+
+    fn get_actual_start_type(&self) -> Result<Terrain> {
+        let start = self.start;
         let possible = [
             self.top(&start),
             self.bottom(&start),
@@ -138,82 +92,43 @@ impl GroundMap {
             [Some(_), _, _, Some(_)] => 'L',
             [_, Some(_), Some(_), _] => '7',
             [Some(_), _, Some(_), _] => 'J',
-            _ => unreachable!(),
+            _ => return Err(AocError::solve("the start is not connected to two pipes")),
         };
-        Terrain {
+        Ok(Terrain {
             kind,
             x: start.x,
             y: start.y,
-        }
+        })
     }
 
-    fn accessible(&self, current: &Terrain) -> Vec<&Terrain> {
-        let mut possible = vec![];
-        match current.kind {
-            '|' => {
-                if let Some(top) = self.top(current) {
-                    possible.push(top)
-                }
-                if let Some(bottom) = self.bottom(current) {
-                    possible.push(bottom)
-                }
-            }
-            '-' => {
-                if let Some(left) = self.left(current) {
-                    possible.push(left)
-                }
-                if let Some(right) = self.right(current) {
-                    possible.push(right)
-                }
-            }
-            'L' => {
-                if let Some(top) = self.top(current) {
-                    possible.push(top)
-                }
-                if let Some(right) = self.right(current) {
-                    possible.push(right)
-                }
-            }
-            'J' => {
-                if let Some(left) = self.left(current) {
-                    possible.push(left)
-                }
-                if let Some(top) = self.top(current) {
-                    possible.push(top)
-                }
-            }
-            '7' => {
-                if let Some(left) = self.left(current) {
-                    possible.push(left)
-                }
-                if let Some(bottom) = self.bottom(current) {
-                    possible.push(bottom)
-                }
-            }
-            'F' => {
-                if let Some(right) = self.right(current) {
-                    possible.push(right)
-                }
-                if let Some(bottom) = self.bottom(current) {
-                    possible.push(bottom)
-                }
-            }
+    fn accessible(&self, current: &Terrain) -> Vec<Terrain> {
+        let ends = match current.kind {
+            '|' => [self.top(current), self.bottom(current)],
+            '-' => [self.left(current), self.right(current)],
+            'L' => [self.top(current), self.right(current)],
+            'J' => [self.left(current), self.top(current)],
+            '7' => [self.left(current), self.bottom(current)],
+            'F' => [self.right(current), self.bottom(current)],
             _ => unreachable!(),
-        }
-        possible
+        };
+        ends.into_iter().flatten().collect()
     }
 
-    fn find_largest_loop(&self) -> Vec<Terrain> {
-        let mut current = *self
-            .accessible(&self.get_actual_start_type())
-            .first()
-            .unwrap();
+    fn find_largest_loop(&self) -> Result<Vec<Terrain>> {
+        let mut current = self.accessible(&self.get_actual_start_type()?)[0];
 
-        let mut pipes: Vec<Terrain> = vec![self.start.clone()];
-        while current.get_hash_key() != self.start.get_hash_key() {
-            let neighbors = self.accessible(current);
+        let mut pipes: Vec<Terrain> = vec![self.start];
+        while (current.x, current.y) != (self.start.x, self.start.y) {
+            let neighbors = self.accessible(&current);
+            if neighbors.is_empty() {
+                return Err(AocError::solve(format!(
+                    "the pipe at line {}, column {} leads nowhere",
+                    current.y + 1,
+                    current.x + 1
+                )));
+            }
             let next;
-            if neighbors[0] == pipes.last().unwrap() {
+            if neighbors[0] == *pipes.last().unwrap() {
                 if neighbors.len() > 1 {
                     next = neighbors[1];
                 } else {
@@ -222,10 +137,10 @@ impl GroundMap {
             } else {
                 next = neighbors[0];
             }
-            pipes.push(current.clone());
+            pipes.push(current);
             current = next;
         }
-        pipes
+        Ok(pipes)
     }
 
     fn search_and_mark(
@@ -233,42 +148,29 @@ impl GroundMap {
         curr: (usize, usize),
         pipes: &HashSet<(usize, usize)>,
     ) -> Vec<(usize, usize)> {
-        if curr.0 >= self.width || curr.1 >= self.height {
-            return vec![];
-        }
         if pipes.contains(&curr) {
             return vec![];
         }
-        let c_key = format!("{:?}x{:?}", curr.0, curr.1);
-        if let Some(v) = self.terrains.get_mut(&c_key) {
-            if v.kind == 'X' {
-                return vec![];
-            }
+        let Some(tile) = self.tiles.at_mut(curr.0, curr.1) else {
+            return vec![];
+        };
+        if *tile == 'X' {
+            return vec![];
         }
-        let neighbors = vec![
-            (curr.0.wrapping_sub(1), curr.1),
-            (curr.0 + 1, curr.1),
-            (curr.0, curr.1.wrapping_sub(1)),
-            (curr.0, curr.1 + 1),
-        ];
-        self.terrains
-            .entry(c_key)
-            .and_modify(|t| t.kind = 'X')
-            .or_insert(Terrain {
-                kind: 'X',
-                x: curr.0,
-                y: curr.1,
-            });
-        neighbors
+        *tile = 'X';
+        self.tiles
+            .neighbors4(curr.0, curr.1)
+            .map(|(position, _)| position)
+            .collect()
     }
 
     fn count_enclosed(&self, area: &Vec<Terrain>) -> usize {
         let mut marked: GroundMap = self.clone();
-        let mut prev = self.start.clone();
-        let mut to_mark = VecDeque::with_capacity(self.width * self.height);
+        let mut prev = self.start;
+        let mut to_mark = VecDeque::with_capacity(self.tiles.items.len());
         let pipe_set: HashSet<(usize, usize)> = area.iter().map(|t| (t.x, t.y)).collect();
         for p in area {
-            let curr = p.clone();
+            let curr = *p;
             match (
                 curr.x as isize - prev.x as isize,
                 curr.y as isize - prev.y as isize,
@@ -290,41 +192,59 @@ impl GroundMap {
                 }
                 _ => {}
             }
-            prev = curr.clone();
+            prev = curr;
         }
 
         while let Some(p) = to_mark.pop_front() {
             to_mark.extend(marked.search_and_mark(p, &pipe_set));
         }
-        let mut marked_count = marked.terrains.values().filter(|t| t.kind == 'X').count();
+        let mut marked_count = marked.tiles.items.iter().filter(|c| **c == 'X').count();
 
-        if let Some(v) = marked.terrains.get("0x0") {
-            if v.kind == 'X' {
-                let total = self.width * self.height;
-                marked_count = total - marked_count - pipe_set.len();
-            }
+        if marked.tiles.at(0, 0) == Some(&'X') {
+            let total = self.tiles.items.len();
+            marked_count = total - marked_count - pipe_set.len();
         }
         marked_count
     }
 }
 
-pub fn solution_day_10_01(file_path: String) -> Option<usize> {
-    let ground_map = fs::read_to_string(file_path)
-        .expect("Invalid File")
-        .parse::<GroundMap>()
-        .unwrap();
-    let pipes = ground_map.find_largest_loop();
-    let val = pipes.len();
-    Some((val / 2) + (val % 2))
+pub struct Day10;
+
+impl Solution for Day10 {
+    const DAY: u8 = 10;
+    type Input = GroundMap;
+    type Output = usize;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        input.parse()
+    }
+
+    fn part1(ground_map: &Self::Input) -> Result<usize> {
+        let pipes = ground_map.find_largest_loop()?;
+        let val = pipes.len();
+        Ok((val / 2) + (val % 2))
+    }
+
+    fn part2(ground_map: &Self::Input) -> Result<usize> {
+        let pipes = ground_map.find_largest_loop()?;
+        Ok(ground_map.count_enclosed(&pipes))
+    }
+}
+
+pub fn solution_day_10_01(file_path: String) -> Result<usize> {
+    solution_day_10_01_from_str(&read_input(file_path)?)
+}
+
+pub fn solution_day_10_01_from_str(input: &str) -> Result<usize> {
+    Day10::solve_part1(input)
+}
+
+pub fn solution_day_10_02(file_path: String) -> Result<usize> {
+    solution_day_10_02_from_str(&read_input(file_path)?)
 }
 
-pub fn solution_day_10_02(file_path: String) -> Option<usize> {
-    let ground_map = fs::read_to_string(file_path)
-        .expect("Invalid File")
-        .parse::<GroundMap>()
-        .unwrap();
-    let pipes = ground_map.find_largest_loop();
-    Some(ground_map.count_enclosed(&pipes))
+pub fn solution_day_10_02_from_str(input: &str) -> Result<usize> {
+    Day10::solve_part2(input)
 }
 
 #[cfg(test)]
@@ -1,46 +1,60 @@
 // Advent of Code 2023 - Day 11
 
-use std::{collections::BTreeSet, fs};
+use std::collections::BTreeSet;
 
-struct Observation {
+use super::{read_input, Param, Params, Solution};
+use crate::error::{AocError, Result};
+
+pub struct Observation {
     galaxies: Vec<(usize, usize)>,
     empty_rows: Vec<usize>,
     empty_cols: Vec<usize>,
 }
 
-#[derive(Debug, PartialEq, Eq)]
-struct ParseObservationError;
-
 impl std::str::FromStr for Observation {
-    type Err = ParseObservationError;
+    type Err = AocError;
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
+    fn from_str(s: &str) -> Result<Self> {
         let mut galaxies: Vec<(usize, usize)> = vec![];
         let mut xs: BTreeSet<usize> = BTreeSet::new();
         let mut ys: BTreeSet<usize> = BTreeSet::new();
 
         for (y, row) in s.lines().enumerate() {
             for (x, c) in row.chars().enumerate() {
-                if c == '#' {
-                    galaxies.push((x, y));
-                    xs.insert(x);
-                    ys.insert(y);
+                match c {
+                    '#' => {
+                        galaxies.push((x, y));
+                        xs.insert(x);
+                        ys.insert(y);
+                    }
+                    '.' => {}
+                    c => {
+                        return Err(AocError::parse(
+                            y + 1,
+                            x + 1,
+                            format!("invalid pixel '{}'", c),
+                        ))
+                    }
                 }
             }
         }
 
         Ok(Observation {
             galaxies,
-            empty_cols: (*xs.first().unwrap()..=*xs.last().unwrap())
-                .filter(|i| !xs.contains(i))
-                .collect(),
-            empty_rows: (*ys.first().unwrap()..=*ys.last().unwrap())
-                .filter(|i| !ys.contains(i))
-                .collect(),
+            empty_cols: gaps(&xs),
+            empty_rows: gaps(&ys),
         })
     }
 }
 
+// The lines between the first and last galaxy that have none.
+fn gaps(seen: &BTreeSet<usize>) -> Vec<usize> {
+    match (seen.first(), seen.last()) {
+        (Some(&first), Some(&last)) => (first..=last).filter(|i| !seen.contains(i)).collect(),
+        _ => vec![],
+    }
+}
+
 impl Observation {
     fn calculate_distance(
         &self,
@@ -80,18 +94,54 @@ impl Observation {
     }
 }
 
-pub fn solution_day_11(file_path: String, multiplier: usize) -> Option<usize> {
-    let data: Observation = fs::read_to_string(file_path)
-        .expect("Invalid File")
-        .parse()
-        .unwrap();
-    Some(data.get_all_galaxy_distances(multiplier))
+pub struct Day11;
+
+impl Solution for Day11 {
+    const DAY: u8 = 11;
+    const PARAMS: &'static [Param] = &[Param::new("multiplier", Some(2), Some(1000000))];
+    type Input = Observation;
+    type Output = usize;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        input.parse()
+    }
+
+    fn part1(data: &Self::Input) -> Result<usize> {
+        Self::part1_with(data, &Params::default())
+    }
+
+    fn part2(data: &Self::Input) -> Result<usize> {
+        Self::part2_with(data, &Params::default())
+    }
+
+    fn part1_with(data: &Self::Input, params: &Params) -> Result<usize> {
+        Ok(data.get_all_galaxy_distances(params.get::<Self>("multiplier", 1)))
+    }
+
+    fn part2_with(data: &Self::Input, params: &Params) -> Result<usize> {
+        Ok(data.get_all_galaxy_distances(params.get::<Self>("multiplier", 2)))
+    }
+}
+
+pub fn solution_day_11(file_path: String, multiplier: usize) -> Result<usize> {
+    solution_day_11_from_str(&read_input(file_path)?, multiplier)
+}
+
+pub fn solution_day_11_from_str(input: &str, multiplier: usize) -> Result<usize> {
+    Day11::parse(input)
+        .map(|data| data.get_all_galaxy_distances(multiplier))
+        .map_err(|e| e.in_day(Day11::DAY))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_no_galaxies() {
+        assert_eq!(solution_day_11_from_str("...\n...", 2), Ok(0));
+    }
+
     #[test]
     fn test_day_11_01() {
         let file_path: String = String::from("src/inputs/day11e.txt");
@@ -1,20 +1,26 @@
 // Advent of Code 2023 - Day 12
-use std::{collections::HashMap, fs};
+use std::collections::HashMap;
 
-#[derive(Debug, Clone)]
-struct DamageReport(String, Vec<usize>);
+use super::{read_input, Solution};
+use crate::error::{parse_lines, parse_token, split_token, AocError, Result};
 
-#[derive(Debug, PartialEq, Eq)]
-struct ParseDamageReportError;
+#[derive(Debug, Clone)]
+pub struct DamageReport(String, Vec<usize>);
 
 impl std::str::FromStr for DamageReport {
-    type Err = ParseDamageReportError;
+    type Err = AocError;
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (map, report) = s.split_once(' ').unwrap();
+    fn from_str(s: &str) -> Result<Self> {
+        let (map, report) = split_token(s, s, " ")?;
+        if let Some(idx) = map.find(|c| !".#?".contains(c)) {
+            return Err(AocError::parse(1, idx + 1, "invalid spring"));
+        }
         Ok(DamageReport(
             map.to_string(),
-            report.split(',').map(|x| x.parse().unwrap()).collect(),
+            report
+                .split(',')
+                .map(|x| parse_token(s, x, "group size"))
+                .collect::<Result<_>>()?,
         ))
     }
 }
@@ -70,33 +76,54 @@ fn find_combinations(inp: &str, report: &[usize], cache: &mut HashMap<String, us
     result
 }
 
-pub fn solution_day_12_01(file_path: String) -> Option<usize> {
-    let mut cache = HashMap::new();
-    let total = fs::read_to_string(file_path)
-        .expect("Invalid File")
-        .lines()
-        .map(|x| {
-            let entry = x.parse::<DamageReport>().unwrap();
-            find_combinations(&entry.0, &entry.1, &mut cache)
-        })
-        .sum();
-
-    Some(total)
+pub struct Day12;
+
+impl Solution for Day12 {
+    const DAY: u8 = 12;
+    type Input = Vec<DamageReport>;
+    type Output = usize;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse_lines(input, str::parse)
+    }
+
+    fn part1(input: &Self::Input) -> Result<usize> {
+        let mut cache = HashMap::new();
+        let total = input
+            .iter()
+            .map(|entry| find_combinations(&entry.0, &entry.1, &mut cache))
+            .sum();
+        Ok(total)
+    }
+
+    fn part2(input: &Self::Input) -> Result<usize> {
+        let mut cache = HashMap::new();
+        let total = input
+            .iter()
+            .map(|entry| {
+                let map = [entry.0.as_str(); 5].join("?");
+                let report = entry.1.repeat(5);
+                find_combinations(&map, &report, &mut cache)
+            })
+            .sum();
+        Ok(total)
+    }
+}
+
+pub fn solution_day_12_01(file_path: String) -> Result<usize> {
+    solution_day_12_01_from_str(&read_input(file_path)?)
+}
+
+pub fn solution_day_12_01_from_str(input: &str) -> Result<usize> {
+    Day12::solve_part1(input)
+}
+
+pub fn solution_day_12_02(file_path: String) -> Result<usize> {
+    solution_day_12_02_from_str(&read_input(file_path)?)
 }
 
-pub fn solution_day_12_02(file_path: String) -> Option<usize> {
-    let mut cache = HashMap::new();
-    let total = fs::read_to_string(file_path)
-        .expect("Invalid File")
-        .lines()
-        .map(|x| {
-            let entry = x.parse::<DamageReport>().unwrap();
-            let map = [entry.0.as_str(); 5].join("?");
-            let report = entry.1.repeat(5);
-            find_combinations(&map, &report, &mut cache)
-        })
-        .sum();
-    Some(total)
+pub fn solution_day_12_02_from_str(input: &str) -> Result<usize> {
+    Day12::solve_part2(input)
 }
 
 #[cfg(test)]
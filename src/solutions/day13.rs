@@ -2,23 +2,23 @@
 
 use derive_deref::Deref;
 use rayon::prelude::*;
-use std::fs;
 
-#[derive(Debug, Clone, PartialEq, Eq, Deref)]
-struct Mirror(Vec<Vec<char>>);
+use super::{read_input, Solution};
+use crate::error::{sections, AocError, Result};
+use crate::utils::collections::Faux2DArray;
 
-#[derive(Debug, PartialEq, Eq)]
-struct ParseMirrorError;
+#[derive(Debug, Clone, PartialEq, Eq, Deref)]
+pub struct Mirror(Faux2DArray<char>);
 
 impl std::str::FromStr for Mirror {
-    type Err = ParseMirrorError;
+    type Err = AocError;
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        Ok(Mirror(s.lines().map(|l| l.chars().collect()).collect()))
+    fn from_str(s: &str) -> Result<Self> {
+        Faux2DArray::from_text(s, |c| "#.".contains(c).then_some(c)).map(Mirror)
     }
 }
 
-fn symmetry_with_error(m1: &[Vec<char>], m2: &[Vec<char>], margin: usize) -> bool {
+fn symmetry_with_error(m1: &[Vec<&char>], m2: &[Vec<&char>], margin: usize) -> bool {
     let mut errors = 0;
 
     let height = m1.len();
@@ -39,121 +39,107 @@ fn symmetry_with_error(m1: &[Vec<char>], m2: &[Vec<char>], margin: usize) -> boo
     true
 }
 
-impl Mirror {
-    fn split_h(&self, position: usize) -> (Mirror, Mirror) {
-        let data = self.split_at(position);
-        (Mirror(data.0.to_vec()), Mirror(data.1.to_vec()))
-    }
-
-    fn split_v(&self, position: usize) -> (Mirror, Mirror) {
-        let mut top: Vec<Vec<char>> = Vec::new();
-        let mut bottom: Vec<Vec<char>> = Vec::new();
-        for row in self.iter() {
-            let data = row.split_at(position);
-            top.push(data.0.to_vec());
-            bottom.push(data.1.to_vec());
+// Rows and columns fold the same way, so this takes either and returns the lines before the fold.
+fn symmetry(lines: &[Vec<&char>], margin: usize) -> Option<usize> {
+    for i in 1..lines.len() {
+        let (top, bottom) = lines.split_at(i);
+        let bottom: Vec<Vec<&char>> = bottom.iter().rev().cloned().collect();
+
+        let min = top.len().min(bottom.len());
+        if symmetry_with_error(
+            &top[top.len() - min..],
+            &bottom[bottom.len() - min..],
+            margin,
+        ) {
+            return Some(top.len());
         }
-        (Mirror(top), Mirror(bottom))
     }
+    None
+}
 
-    fn flip_v(&self) -> Mirror {
-        let data: Vec<Vec<char>> = self
-            .iter()
-            .map(|row| row.iter().rev().copied().collect())
-            .collect();
-        Mirror(data)
+impl Mirror {
+    fn horizontal_symmetry(&self, margin: usize) -> Option<usize> {
+        symmetry(&self.rows().collect::<Vec<_>>(), margin)
     }
 
-    fn flip_h(&self) -> Mirror {
-        let data: Vec<Vec<char>> = self.iter().rev().cloned().collect();
-        Mirror(data)
+    fn vertical_symmetry(&self, margin: usize) -> Option<usize> {
+        symmetry(&self.cols().collect::<Vec<_>>(), margin)
     }
 
-    fn horizontal_symmetry(&self, margin: usize) -> Option<(Mirror, Mirror)> {
-        let height = self.len();
-        for i in 1..height {
-            let (top, bottom) = self.split_h(i);
-            let bottom = bottom.flip_h();
-
-            let min = top.len().min(bottom.len());
-            if symmetry_with_error(
-                &top[top.len() - min..],
-                &bottom[bottom.len() - min..],
-                margin,
-            ) {
-                return Some((top, bottom));
-            }
+    fn score(&self, margin: usize) -> Option<usize> {
+        if let Some(above) = self.horizontal_symmetry(margin) {
+            return Some(100 * above);
         }
-        None
+        self.vertical_symmetry(margin)
     }
+}
 
-    fn vertical_symmetry(&self, margin: usize) -> Option<(Mirror, Mirror)> {
-        let width = self[0].len();
-        for i in 1..width {
-            let (left, right) = self.split_v(i);
-            let right = right.flip_v();
-            let min = left[0].len().min(right[0].len());
-            let left_data: Vec<Vec<char>> = left
-                .iter()
-                .map(|row| row[row.len() - min..].to_vec())
-                .collect();
-            let right_data: Vec<Vec<char>> = right
-                .iter()
-                .map(|row| row[row.len() - min..].to_vec())
-                .collect();
-            if symmetry_with_error(&left_data, &right_data, margin) {
-                return Some((left, right));
-            }
-        }
-        None
-    }
+fn total_score(mirrors: &[Mirror], margin: usize) -> Result<usize> {
+    mirrors
+        .par_iter()
+        .enumerate()
+        .map(|(idx, m)| {
+            m.score(margin)
+                .ok_or_else(|| AocError::solve(format!("pattern {} has no reflection", idx + 1)))
+        })
+        .sum()
+}
 
-    fn symmetry(&self, margin: usize) -> Option<((Mirror, Mirror), String)> {
-        if let Some(result) = self.horizontal_symmetry(margin) {
-            return Some((result, String::from("horizontal")));
-        }
-        if let Some(result) = self.vertical_symmetry(margin) {
-            return Some((result, String::from("vertical")));
-        }
+pub struct Day13;
+
+impl Solution for Day13 {
+    const DAY: u8 = 13;
+    type Input = Vec<Mirror>;
+    type Output = usize;
 
-        None
+    fn parse(input: &str) -> Result<Self::Input> {
+        sections(input)
+            .map(|(offset, l)| l.parse().map_err(|e: AocError| e.below(offset)))
+            .collect()
     }
-    fn score(&self, margin: usize) -> usize {
-        if let Some((data, s_type)) = self.symmetry(margin) {
-            return match s_type.as_str() {
-                "horizontal" => 100 * data.0.len(),
-                "vertical" => data.0[0].len(),
-                _ => 0,
-            };
-        }
-        0
+
+    fn part1(mirrors: &Self::Input) -> Result<usize> {
+        total_score(mirrors, 0)
+    }
+
+    fn part2(mirrors: &Self::Input) -> Result<usize> {
+        total_score(mirrors, 1)
     }
 }
 
-pub fn solution_day_13_01(file_path: String) -> Option<usize> {
-    let result = fs::read_to_string(file_path)
-        .expect("Invalid Input file.")
-        .split("\n\n")
-        .par_bridge()
-        .map(|l| l.parse::<Mirror>().unwrap().score(0))
-        .sum();
-    Some(result)
+pub fn solution_day_13_01(file_path: String) -> Result<usize> {
+    solution_day_13_01_from_str(&read_input(file_path)?)
 }
 
-pub fn solution_day_13_02(file_path: String) -> Option<usize> {
-    let result = fs::read_to_string(file_path)
-        .expect("Invalid Input file.")
-        .split("\n\n")
-        .par_bridge()
-        .map(|l| l.parse::<Mirror>().unwrap().score(1))
-        .sum();
-    Some(result)
+pub fn solution_day_13_01_from_str(input: &str) -> Result<usize> {
+    Day13::solve_part1(input)
+}
+
+pub fn solution_day_13_02(file_path: String) -> Result<usize> {
+    solution_day_13_02_from_str(&read_input(file_path)?)
+}
+
+pub fn solution_day_13_02_from_str(input: &str) -> Result<usize> {
+    Day13::solve_part2(input)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_no_reflection() {
+        assert_eq!(
+            solution_day_13_01_from_str("##\n..\n\n#.\n.#"),
+            Err(AocError::solve("pattern 2 has no reflection").in_day(13))
+        );
+        // The second pattern is only symmetric without the smudge.
+        assert_eq!(
+            solution_day_13_02_from_str("#.\n##\n\n##\n.."),
+            Err(AocError::solve("pattern 2 has no reflection").in_day(13))
+        );
+    }
+
     #[test]
     fn test_day_13_01() {
         let file_path: String = String::from("src/inputs/day13e.txt");
@@ -2,22 +2,22 @@
 
 use std::collections::hash_map::DefaultHasher;
 use std::collections::HashMap;
-use std::fs;
 use std::hash::{Hash, Hasher};
 
 use derive_deref::{Deref, DerefMut};
 
-#[derive(Debug, Clone, Hash, Deref, DerefMut)]
-struct Platform(Vec<Vec<char>>);
+use super::{read_input, Solution};
+use crate::error::{AocError, Result};
+use crate::utils::collections::Faux2DArray;
 
-#[derive(Debug, PartialEq, Eq)]
-struct ParsePlatformError;
+#[derive(Debug, Clone, Hash, Deref, DerefMut)]
+pub struct Platform(Faux2DArray<char>);
 
 impl std::str::FromStr for Platform {
-    type Err = ParsePlatformError;
+    type Err = AocError;
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        Ok(Platform(s.lines().map(|l| l.chars().collect()).collect()))
+    fn from_str(s: &str) -> Result<Self> {
+        Faux2DArray::from_text(s, |c| "O#.".contains(c).then_some(c)).map(Platform)
     }
 }
 
@@ -29,24 +29,24 @@ impl Platform {
     }
 
     fn tilt_north(&mut self) {
-        let width = self[0].len();
-        let height = self.len();
+        let width = self.width;
+        let height = self.height();
         for y in 0..height {
             for x in 0..width {
-                match self[y][x] {
+                match self[(x, y)] {
                     '#' | '.' => (),
                     'O' => {
                         let mut move_to = y;
                         for i in (0..y).rev() {
-                            match self[i][x] {
+                            match self[(x, i)] {
                                 '#' | 'O' => break,
                                 '.' => move_to = i,
                                 _ => (),
                             }
                         }
-                        let old = self[move_to][x];
-                        self[move_to][x] = 'O';
-                        self[y][x] = old;
+                        let old = self[(x, move_to)];
+                        self[(x, move_to)] = 'O';
+                        self[(x, y)] = old;
                     }
                     _ => unreachable!(),
                 }
@@ -55,25 +55,25 @@ impl Platform {
     }
 
     fn tilt_south(&mut self) {
-        let width = self[0].len();
-        let height = self.len();
+        let width = self.width;
+        let height = self.height();
         for y in (0..height).rev() {
             for x in 0..width {
-                match self[y][x] {
+                match self[(x, y)] {
                     '#' | '.' => (),
                     'O' => {
                         let mut move_to = y;
                         for i in y + 1..height {
-                            match self[i][x] {
+                            match self[(x, i)] {
                                 '#' | 'O' => break,
                                 '.' => move_to = i,
                                 _ => (),
                             }
                         }
 
-                        let old = self[move_to][x];
-                        self[move_to][x] = 'O';
-                        self[y][x] = old;
+                        let old = self[(x, move_to)];
+                        self[(x, move_to)] = 'O';
+                        self[(x, y)] = old;
                     }
                     _ => unreachable!(),
                 }
@@ -82,25 +82,25 @@ impl Platform {
     }
 
     fn tilt_east(&mut self) {
-        let width = self[0].len();
-        let height = self.len();
+        let width = self.width;
+        let height = self.height();
         for y in 0..height {
             for x in (0..width).rev() {
-                match self[y][x] {
+                match self[(x, y)] {
                     '#' | '.' => (),
                     'O' => {
                         let mut move_to = x;
                         for i in x + 1..width {
-                            match self[y][i] {
+                            match self[(i, y)] {
                                 '#' | 'O' => break,
                                 '.' => move_to = i,
                                 _ => (),
                             }
                         }
 
-                        let old = self[y][move_to];
-                        self[y][move_to] = 'O';
-                        self[y][x] = old;
+                        let old = self[(move_to, y)];
+                        self[(move_to, y)] = 'O';
+                        self[(x, y)] = old;
                     }
                     _ => unreachable!(),
                 }
@@ -109,25 +109,25 @@ impl Platform {
     }
 
     fn tilt_west(&mut self) {
-        let width = self[0].len();
-        let height = self.len();
+        let width = self.width;
+        let height = self.height();
         for y in 0..height {
             for x in 0..width {
-                match self[y][x] {
+                match self[(x, y)] {
                     '#' | '.' => (),
                     'O' => {
                         let mut move_to = x;
                         for i in (0..x).rev() {
-                            match self[y][i] {
+                            match self[(i, y)] {
                                 '#' | 'O' => break,
                                 '.' => move_to = i,
                                 _ => (),
                             }
                         }
 
-                        let old = self[y][move_to];
-                        self[y][move_to] = 'O';
-                        self[y][x] = old;
+                        let old = self[(move_to, y)];
+                        self[(move_to, y)] = 'O';
+                        self[(x, y)] = old;
                     }
                     _ => unreachable!(),
                 }
@@ -144,10 +144,10 @@ impl Platform {
 
     fn calculate_load(&self) -> usize {
         let mut load = 0;
-        let height = self.len();
-        for (y, row) in self.iter().enumerate() {
+        let height = self.height();
+        for (y, row) in self.rows().enumerate() {
             for c in row.iter() {
-                if *c == 'O' {
+                if **c == 'O' {
                     load += height - y;
                 }
             }
@@ -156,37 +156,59 @@ impl Platform {
     }
 }
 
-pub fn solution_day_14_01(file_path: String) -> Option<usize> {
-    let mut platform = fs::read_to_string(file_path)
-        .expect("Invalid input file.")
-        .parse::<Platform>()
-        .unwrap();
-    platform.tilt_north();
-    Some(platform.calculate_load())
-}
+pub struct Day14;
 
-pub fn solution_day_14_02(file_path: String) -> Option<usize> {
-    let mut platform = fs::read_to_string(file_path)
-        .expect("Invalid input file.")
-        .parse::<Platform>()
-        .unwrap();
-    let mut seen: HashMap<u64, usize> = HashMap::new();
-    for i in 0..1000000000 {
-        let hash = platform.get_hash();
-        if let std::collections::hash_map::Entry::Vacant(e) = seen.entry(hash) {
-            platform.tilt_cycle();
-            e.insert(i);
-        } else {
-            let cycle = i - seen.get(&hash).unwrap();
-            let remaining = 1000000000 - i;
-            let remaining = remaining % cycle;
-            for _ in 0..remaining {
+impl Solution for Day14 {
+    const DAY: u8 = 14;
+    type Input = Platform;
+    type Output = usize;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        input.parse()
+    }
+
+    fn part1(platform: &Self::Input) -> Result<usize> {
+        let mut platform = platform.clone();
+        platform.tilt_north();
+        Ok(platform.calculate_load())
+    }
+
+    fn part2(platform: &Self::Input) -> Result<usize> {
+        let mut platform = platform.clone();
+        let mut seen: HashMap<u64, usize> = HashMap::new();
+        for i in 0..1000000000 {
+            let hash = platform.get_hash();
+            if let std::collections::hash_map::Entry::Vacant(e) = seen.entry(hash) {
                 platform.tilt_cycle();
+                e.insert(i);
+            } else {
+                let cycle = i - seen.get(&hash).unwrap();
+                let remaining = 1000000000 - i;
+                let remaining = remaining % cycle;
+                for _ in 0..remaining {
+                    platform.tilt_cycle();
+                }
+                break;
             }
-            break;
         }
+        Ok(platform.calculate_load())
     }
-    Some(platform.calculate_load())
+}
+
+pub fn solution_day_14_01(file_path: String) -> Result<usize> {
+    solution_day_14_01_from_str(&read_input(file_path)?)
+}
+
+pub fn solution_day_14_01_from_str(input: &str) -> Result<usize> {
+    Day14::solve_part1(input)
+}
+
+pub fn solution_day_14_02(file_path: String) -> Result<usize> {
+    solution_day_14_02_from_str(&read_input(file_path)?)
+}
+
+pub fn solution_day_14_02_from_str(input: &str) -> Result<usize> {
+    Day14::solve_part2(input)
 }
 
 #[cfg(test)]
@@ -1,37 +1,35 @@
 // Advent of Code 2023 - Day 15
 
-use std::{collections::HashMap, fs};
+use std::collections::HashMap;
 
 use derive_deref::{Deref, DerefMut};
 use indexmap::IndexMap;
 
+use super::{read_input, Solution};
+use crate::error::{column_of, parse_lines, parse_token, split_token, AocError, Result};
+
 fn get_hash(seq: &str) -> usize {
     let res = seq
         .chars()
         .fold(0, |acc, c| ((acc + (c as u32)) * 17) % 256);
     res as usize
 }
-enum Operation {
+pub enum Operation {
     Set((String, usize)),
     Remove(String),
 }
 
-#[derive(Debug, PartialEq, Eq)]
-struct ParseOperationError;
-
-impl std::str::FromStr for Operation {
-    type Err = ParseOperationError;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        if s.ends_with('-') {
-            Ok(Operation::Remove(s[0..s.len() - 1].to_string()))
-        } else {
-            let (name, val) = s.split_once('=').unwrap();
-            Ok(Operation::Set((
-                name.to_string(),
-                val.parse::<usize>().unwrap(),
-            )))
-        }
+// Steps are comma separated, so errors are reported against the whole line.
+fn parse_operation(line: &str, step: &str) -> Result<Operation> {
+    if let Some(name) = step.strip_suffix('-') {
+        Ok(Operation::Remove(name.to_string()))
+    } else {
+        let (name, val) = split_token(line, step, "=")
+            .map_err(|_| AocError::parse(1, column_of(line, step), "expected \"=\" or \"-\""))?;
+        Ok(Operation::Set((
+            name.to_string(),
+            parse_token(line, val, "focal length")?,
+        )))
     }
 }
 
@@ -43,17 +41,17 @@ impl BoxArray {
         Self(HashMap::new())
     }
 
-    fn operate(&mut self, op: Operation) {
+    fn operate(&mut self, op: &Operation) {
         match op {
             Operation::Set((name, val)) => {
-                let hash = get_hash(&name);
+                let hash = get_hash(name);
                 let entry = self.entry(hash).or_default();
-                entry.insert(name, val);
+                entry.insert(name.clone(), *val);
             }
             Operation::Remove(name) => {
-                let hash = get_hash(&name);
+                let hash = get_hash(name);
                 if let Some(entry) = self.get_mut(&hash) {
-                    entry.shift_remove(&name);
+                    entry.shift_remove(name);
                 }
             }
         }
@@ -70,39 +68,50 @@ impl BoxArray {
     }
 }
 
-#[derive(Debug, PartialEq, Eq)]
-struct ParseBoxArrayError;
+pub struct Day15;
+
+impl Solution for Day15 {
+    const DAY: u8 = 15;
+    // Part one hashes the raw steps, part two runs them.
+    type Input = Vec<(String, Operation)>;
+    type Output = usize;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        let lines = parse_lines(input, |l| {
+            l.split(',')
+                .map(|s| Ok((s.to_string(), parse_operation(l, s)?)))
+                .collect::<Result<Vec<_>>>()
+        })?;
+        Ok(lines.into_iter().flatten().collect())
+    }
 
-impl std::str::FromStr for BoxArray {
-    type Err = ParseBoxArrayError;
+    fn part1(steps: &Self::Input) -> Result<usize> {
+        Ok(steps.iter().map(|(s, _)| get_hash(s)).sum::<usize>())
+    }
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut res = Self::new();
-        for line in s.lines() {
-            for ins in line.split(',') {
-                res.operate(ins.parse::<Operation>().unwrap());
-            }
+    fn part2(steps: &Self::Input) -> Result<usize> {
+        let mut boxes = BoxArray::new();
+        for (_, step) in steps {
+            boxes.operate(step);
         }
-        Ok(res)
+        Ok(boxes.calculate_focusing_power())
     }
 }
 
-pub fn solution_day_15_01(file_path: String) -> Option<usize> {
-    let res = fs::read_to_string(file_path)
-        .expect("Invalid Input File.")
-        .lines()
-        .flat_map(|l| l.split(',').map(get_hash))
-        .sum::<usize>();
-    Some(res)
+pub fn solution_day_15_01(file_path: String) -> Result<usize> {
+    solution_day_15_01_from_str(&read_input(file_path)?)
+}
+
+pub fn solution_day_15_01_from_str(input: &str) -> Result<usize> {
+    Day15::solve_part1(input)
 }
 
-pub fn solution_day_15_02(file_path: String) -> Option<usize> {
-    let boxes: BoxArray = fs::read_to_string(file_path)
-        .expect("Invalid Input File.")
-        .parse()
-        .unwrap();
+pub fn solution_day_15_02(file_path: String) -> Result<usize> {
+    solution_day_15_02_from_str(&read_input(file_path)?)
+}
 
-    Some(boxes.calculate_focusing_power())
+pub fn solution_day_15_02_from_str(input: &str) -> Result<usize> {
+    Day15::solve_part2(input)
 }
 
 #[cfg(test)]
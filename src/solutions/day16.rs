@@ -1,7 +1,11 @@
 // Advent of Code 2023 - Day 16
 use rayon::prelude::*;
+use std::collections::HashSet;
 use std::sync::mpsc::channel;
-use std::{collections::HashSet, fs};
+
+use super::{read_input, Solution};
+use crate::error::{AocError, Result};
+use crate::utils::collections::Faux2DArray;
 
 #[derive(Debug, PartialEq, Eq, Clone, Hash)]
 enum MovementDirection {
@@ -20,15 +24,17 @@ enum RoomTile {
     HorizontalSplitter,
 }
 
-impl std::convert::From<char> for RoomTile {
-    fn from(s: char) -> Self {
+impl std::convert::TryFrom<char> for RoomTile {
+    type Error = char;
+
+    fn try_from(s: char) -> std::result::Result<Self, Self::Error> {
         match s {
-            '.' => Self::Empty,
-            '/' => Self::RightTiltedMirror,
-            '\\' => Self::LeftTiltedMirror,
-            '|' => Self::VerticalSplitter,
-            '-' => Self::HorizontalSplitter,
-            _ => unreachable!(),
+            '.' => Ok(Self::Empty),
+            '/' => Ok(Self::RightTiltedMirror),
+            '\\' => Ok(Self::LeftTiltedMirror),
+            '|' => Ok(Self::VerticalSplitter),
+            '-' => Ok(Self::HorizontalSplitter),
+            c => Err(c),
         }
     }
 }
@@ -40,25 +46,19 @@ struct Photon {
 }
 
 #[derive(Debug, PartialEq, Eq, Clone)]
-struct MirrorRoom {
-    room: Vec<Vec<RoomTile>>,
+pub struct MirrorRoom {
+    room: Faux2DArray<RoomTile>,
     width: isize,
     height: isize,
 }
 
-#[derive(Debug, PartialEq, Eq)]
-struct ParseMirrorRoomError;
-
 impl std::str::FromStr for MirrorRoom {
-    type Err = ParseMirrorRoomError;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let room: Vec<Vec<RoomTile>> = s
-            .lines()
-            .map(|l| l.chars().map(RoomTile::from).collect())
-            .collect();
-        let width = room[0].len() as isize;
-        let height = room.len() as isize;
+    type Err = AocError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let room: Faux2DArray<RoomTile> = s.parse()?;
+        let width = room.width as isize;
+        let height = room.height() as isize;
         Ok(Self {
             room,
             width,
@@ -75,10 +75,7 @@ impl MirrorRoom {
             MovementDirection::Upward => (photon.position.0, photon.position.1 - 1),
             MovementDirection::Downward => (photon.position.0, photon.position.1 + 1),
         };
-        if new_pos.0 < 0 || new_pos.0 >= self.width || new_pos.1 < 0 || new_pos.1 >= self.height {
-            return None;
-        }
-        let tile = &self.room[new_pos.1 as usize][new_pos.0 as usize];
+        let tile = self.room.get(new_pos.0, new_pos.1)?;
         let mut directions = vec![];
 
         match tile {
@@ -141,55 +138,78 @@ impl MirrorRoom {
     }
 }
 
-pub fn solution_day_16_01(file_path: String) -> Option<usize> {
-    let mirror_room: MirrorRoom = fs::read_to_string(file_path).unwrap().parse().unwrap();
-    Some(mirror_room.find_photons(&Photon {
-        position: (-1, 0),
-        direction: MovementDirection::Rightward,
-    }))
-}
+pub struct Day16;
+
+impl Solution for Day16 {
+    const DAY: u8 = 16;
+    type Input = MirrorRoom;
+    type Output = usize;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        input.parse()
+    }
+
+    fn part1(mirror_room: &Self::Input) -> Result<usize> {
+        Ok(mirror_room.find_photons(&Photon {
+            position: (-1, 0),
+            direction: MovementDirection::Rightward,
+        }))
+    }
 
-pub fn solution_day_16_02(file_path: String) -> Option<usize> {
-    let mirror_room: MirrorRoom = fs::read_to_string(file_path).unwrap().parse().unwrap();
-
-    let (sender, receiver) = channel();
-    (0..mirror_room.width)
-        .into_par_iter()
-        .for_each_with(sender, |s, x| {
-            s.send(mirror_room.find_photons(&Photon {
-                position: (x, -1),
-                direction: MovementDirection::Downward,
-            }))
-            .unwrap();
-            s.send(mirror_room.find_photons(&Photon {
-                position: (x, mirror_room.height),
-                direction: MovementDirection::Upward,
-            }))
-            .unwrap();
-        });
-    let (sender2, receiver2) = channel();
-    (0..mirror_room.height)
-        .into_par_iter()
-        .for_each_with(sender2, |s, y| {
-            s.send(mirror_room.find_photons(&Photon {
-                position: (-1, y),
-                direction: MovementDirection::Rightward,
-            }))
-            .unwrap();
-            s.send(mirror_room.find_photons(&Photon {
-                position: (mirror_room.width, y),
-                direction: MovementDirection::LeftWard,
-            }))
-            .unwrap();
-        });
-
-    Some(
-        receiver
+    fn part2(mirror_room: &Self::Input) -> Result<usize> {
+        let (sender, receiver) = channel();
+        (0..mirror_room.width)
+            .into_par_iter()
+            .for_each_with(sender, |s, x| {
+                s.send(mirror_room.find_photons(&Photon {
+                    position: (x, -1),
+                    direction: MovementDirection::Downward,
+                }))
+                .unwrap();
+                s.send(mirror_room.find_photons(&Photon {
+                    position: (x, mirror_room.height),
+                    direction: MovementDirection::Upward,
+                }))
+                .unwrap();
+            });
+        let (sender2, receiver2) = channel();
+        (0..mirror_room.height)
+            .into_par_iter()
+            .for_each_with(sender2, |s, y| {
+                s.send(mirror_room.find_photons(&Photon {
+                    position: (-1, y),
+                    direction: MovementDirection::Rightward,
+                }))
+                .unwrap();
+                s.send(mirror_room.find_photons(&Photon {
+                    position: (mirror_room.width, y),
+                    direction: MovementDirection::LeftWard,
+                }))
+                .unwrap();
+            });
+
+        Ok(receiver
             .iter()
             .max()
             .unwrap()
-            .max(receiver2.iter().max().unwrap()),
-    )
+            .max(receiver2.iter().max().unwrap()))
+    }
+}
+
+pub fn solution_day_16_01(file_path: String) -> Result<usize> {
+    solution_day_16_01_from_str(&read_input(file_path)?)
+}
+
+pub fn solution_day_16_01_from_str(input: &str) -> Result<usize> {
+    Day16::solve_part1(input)
+}
+
+pub fn solution_day_16_02(file_path: String) -> Result<usize> {
+    solution_day_16_02_from_str(&read_input(file_path)?)
+}
+
+pub fn solution_day_16_02_from_str(input: &str) -> Result<usize> {
+    Day16::solve_part2(input)
 }
 
 #[cfg(test)]
@@ -2,30 +2,23 @@
 
 use std::collections::{BinaryHeap, HashMap};
 
+use super::{read_input, Solution};
+use crate::error::{AocError, Result};
+use crate::utils::collections::Faux2DArray;
+
 #[derive(Debug, Clone, Hash)]
-struct Graph {
-    nodes: Vec<Vec<usize>>,
+pub struct Graph {
+    nodes: Faux2DArray<usize>,
     width: usize,
     height: usize,
 }
 
-#[derive(Debug, PartialEq, Eq)]
-struct ParseGraphError;
-
 impl std::str::FromStr for Graph {
-    type Err = ParseGraphError;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let nodes: Vec<Vec<usize>> = s
-            .lines()
-            .map(|l| {
-                l.chars()
-                    .map(|c| c.to_digit(10).unwrap() as usize)
-                    .collect()
-            })
-            .collect();
-        let width: usize = nodes[0].len();
-        let height: usize = nodes.len();
+    type Err = AocError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let nodes = Faux2DArray::from_text(s, |c| c.to_digit(10).map(|v| v as usize))?;
+        let (width, height) = (nodes.width, nodes.height());
         Ok(Graph {
             nodes,
             width,
@@ -52,7 +45,7 @@ impl Ord for Record {
 
 impl PartialOrd for Record {
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        Some(other.0.cmp(&self.0))
+        Some(self.cmp(other))
     }
 }
 
@@ -86,7 +79,7 @@ impl Graph {
                     return None;
                 }
                 let (nx, ny) = (cursor.pos.0 as isize + dir.0, cursor.pos.1 as isize + dir.1);
-                if nx < 0 || ny < 0 || nx as usize >= self.width || ny as usize >= self.height {
+                if !self.nodes.contains(nx, ny) {
                     return None;
                 }
                 Some(Cursor {
@@ -97,7 +90,7 @@ impl Graph {
             })
     }
 
-    fn find_shortest(&self, min_moves: usize, max_moves: usize) -> usize {
+    fn find_shortest(&self, min_moves: usize, max_moves: usize) -> Result<usize> {
         let mut bests = HashMap::new();
         let mut boundary = BinaryHeap::new();
 
@@ -116,7 +109,7 @@ impl Graph {
             bests.insert(cursor.clone(), val);
             for next in self.find_neighbors(&cursor, min_moves, max_moves) {
                 if !bests.contains_key(&next) {
-                    let val = bests.get(&cursor).unwrap() + self.nodes[next.pos.1][next.pos.0];
+                    let val = bests.get(&cursor).unwrap() + self.nodes[next.pos];
                     boundary.push(Record(val, next));
                 }
             }
@@ -135,18 +128,45 @@ impl Graph {
                 }
             })
             .min();
-        *res.unwrap()
+        res.copied()
+            .ok_or_else(|| AocError::solve("the crucible can't reach the factory"))
+    }
+}
+
+pub struct Day17;
+
+impl Solution for Day17 {
+    const DAY: u8 = 17;
+    type Input = Graph;
+    type Output = usize;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        input.parse()
     }
+
+    fn part1(graph: &Self::Input) -> Result<usize> {
+        graph.find_shortest(1, 3)
+    }
+
+    fn part2(graph: &Self::Input) -> Result<usize> {
+        graph.find_shortest(4, 10)
+    }
+}
+
+pub fn solution_day_17_01(file_path: String) -> Result<usize> {
+    solution_day_17_01_from_str(&read_input(file_path)?)
+}
+
+pub fn solution_day_17_01_from_str(input: &str) -> Result<usize> {
+    Day17::solve_part1(input)
 }
 
-pub fn solution_day_17_01(file_path: String) -> Option<usize> {
-    let graph: Graph = std::fs::read_to_string(file_path).unwrap().parse().unwrap();
-    Some(graph.find_shortest(1, 3))
+pub fn solution_day_17_02(file_path: String) -> Result<usize> {
+    solution_day_17_02_from_str(&read_input(file_path)?)
 }
 
-pub fn solution_day_17_02(file_path: String) -> Option<usize> {
-    let graph: Graph = std::fs::read_to_string(file_path).unwrap().parse().unwrap();
-    Some(graph.find_shortest(4, 10))
+pub fn solution_day_17_02_from_str(input: &str) -> Result<usize> {
+    Day17::solve_part2(input)
 }
 
 #[cfg(test)]
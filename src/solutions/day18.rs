@@ -1,87 +1,147 @@
-use std::fs;
+use super::{read_input, Solution};
+use crate::error::{column_of, parse_lines, parse_token, AocError, Result};
 
 // Advent of Code 2023 - Day 18
 
-struct Instruction {
+pub struct Instruction {
     dir: u8,
     len: usize,
     c: usize,
 }
 
 impl std::str::FromStr for Instruction {
-    type Err = ();
+    type Err = AocError;
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut s = s.splitn(3, ' ');
-        let dir = match s.next().unwrap() {
+    fn from_str(line: &str) -> Result<Self> {
+        let mut s = line.splitn(3, ' ');
+        let mut next = |what| {
+            s.next()
+                .ok_or_else(|| AocError::parse(1, line.len() + 1, format!("missing {}", what)))
+        };
+        let dir_token = next("direction")?;
+        let dir = match dir_token {
             "R" => 0,
             "D" => 1,
             "L" => 2,
             "U" => 3,
-            _ => unreachable!(),
+            d => {
+                return Err(AocError::parse(
+                    1,
+                    column_of(line, dir_token),
+                    format!("invalid direction \"{}\"", d),
+                ))
+            }
         };
-        let len = s.next().unwrap().parse::<usize>().unwrap();
-        let c = usize::from_str_radix(
-            s.next()
-                .unwrap()
-                .trim_matches(|s| s == '(' || s == ')' || s == '#'),
-            16,
-        )
-        .unwrap();
+        let len = parse_token(line, next("length")?, "length")?;
+        let color = next("color")?;
+        let c = usize::from_str_radix(color.trim_matches(|s| s == '(' || s == ')' || s == '#'), 16)
+            .map_err(|_| {
+                AocError::parse(
+                    1,
+                    column_of(line, color),
+                    format!("invalid color \"{}\"", color),
+                )
+            })?;
 
         Ok(Self { dir, len, c })
     }
 }
 
-fn get_end(start: (isize, isize), dir: u8, len: usize) -> (isize, isize) {
+fn get_end(start: (isize, isize), dir: u8, len: usize) -> Option<(isize, isize)> {
     match dir {
-        0 => (start.0.wrapping_add_unsigned(len), start.1),
-        1 => (start.0, start.1.wrapping_add_unsigned(len)),
-        2 => (start.0.wrapping_sub_unsigned(len), start.1),
-        3 => (start.0, start.1.wrapping_sub_unsigned(len)),
-        _ => unreachable!(),
+        0 => Some((start.0.checked_add_unsigned(len)?, start.1)),
+        1 => Some((start.0, start.1.checked_add_unsigned(len)?)),
+        2 => Some((start.0.checked_sub_unsigned(len)?, start.1)),
+        3 => Some((start.0, start.1.checked_sub_unsigned(len)?)),
+        _ => None,
     }
 }
 
-fn shoelace_area(
+// None when the lagoon is too large to measure.
+fn checked_shoelace_area(
     instructions: &[Instruction],
     vector_func: fn(&Instruction) -> (u8, usize),
-) -> usize {
-    let mut perimeter = 0;
-    let mut sum = 0;
+) -> Option<usize> {
+    let mut perimeter: usize = 0;
+    let mut sum: isize = 0;
     let mut prev = (0, 0);
     for i in instructions {
         let (dir, len) = vector_func(i);
 
-        let next = get_end(prev, dir, len);
-        sum += (prev.1 + next.1) * (prev.0 - next.0);
-        perimeter += len;
+        let next = get_end(prev, dir, len)?;
+        let term = prev
+            .1
+            .checked_add(next.1)?
+            .checked_mul(prev.0.checked_sub(next.0)?)?;
+        sum = sum.checked_add(term)?;
+        perimeter = perimeter.checked_add(len)?;
         prev = next;
     }
-    perimeter.wrapping_add_signed(sum) / 2 + 1
+    // The sign only depends on which way the trench goes around.
+    Some(perimeter.checked_add(sum.unsigned_abs())? / 2 + 1)
+}
+
+fn shoelace_area(
+    instructions: &[Instruction],
+    vector_func: fn(&Instruction) -> (u8, usize),
+) -> Result<usize> {
+    checked_shoelace_area(instructions, vector_func)
+        .ok_or_else(|| AocError::solve("the lagoon is too large"))
+}
+
+pub struct Day18;
+
+impl Solution for Day18 {
+    const DAY: u8 = 18;
+    type Input = Vec<Instruction>;
+    type Output = usize;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse_lines(input, str::parse)
+    }
+
+    fn part1(plan: &Self::Input) -> Result<usize> {
+        shoelace_area(plan, |i| (i.dir, i.len))
+    }
+
+    fn part2(plan: &Self::Input) -> Result<usize> {
+        shoelace_area(plan, |i| ((i.c & 3) as u8, (i.c >> 4)))
+    }
 }
-pub fn solution_day_18_01(file_path: String) -> Option<usize> {
-    let plan: Vec<Instruction> = fs::read_to_string(file_path)
-        .expect("Invalid Input File.")
-        .lines()
-        .map(|l| l.parse::<Instruction>().unwrap())
-        .collect();
-    Some(shoelace_area(&plan, |i| (i.dir, i.len)))
+
+pub fn solution_day_18_01(file_path: String) -> Result<usize> {
+    solution_day_18_01_from_str(&read_input(file_path)?)
 }
 
-pub fn solution_day_18_02(file_path: String) -> Option<usize> {
-    let plan: Vec<Instruction> = fs::read_to_string(file_path)
-        .expect("Invalid Input File.")
-        .lines()
-        .map(|l| l.parse::<Instruction>().unwrap())
-        .collect();
-    Some(shoelace_area(&plan, |i| ((i.c & 3) as u8, (i.c >> 4))))
+pub fn solution_day_18_01_from_str(input: &str) -> Result<usize> {
+    Day18::solve_part1(input)
+}
+
+pub fn solution_day_18_02(file_path: String) -> Result<usize> {
+    solution_day_18_02_from_str(&read_input(file_path)?)
+}
+
+pub fn solution_day_18_02_from_str(input: &str) -> Result<usize> {
+    Day18::solve_part2(input)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_large_lagoon() {
+        let input =
+            "R 1 (#fffffffffff0)\nD 1 (#fffffffffff1)\nL 1 (#fffffffffff2)\nU 1 (#fffffffffff3)";
+        assert_eq!(
+            solution_day_18_02_from_str(input).unwrap_err(),
+            AocError::solve("the lagoon is too large").in_day(18)
+        );
+        // The same trench, counter-clockwise.
+        let input = "D 2 (#000000)\nR 2 (#000000)\nU 2 (#000000)\nL 2 (#000000)";
+        assert_eq!(solution_day_18_01_from_str(input), Ok(9));
+    }
+
     #[test]
     fn test_day_18_01() {
         let file_path: String = String::from("src/inputs/day18e.txt");
@@ -1,26 +1,35 @@
 // Advent of Code 2023 - Day 19
 
-use std::{collections::HashMap, fs};
+use std::collections::HashMap;
 
 use derive_deref::{Deref, DerefMut};
 
+use super::{read_input, Solution};
+use crate::error::{column_of, parse_lines, parse_token, split_token, AocError, Result};
+
 #[derive(Debug, Clone, Deref)]
 struct Part(HashMap<char, usize>);
 
-#[derive(Debug, Eq, PartialEq)]
-struct ParsePartError;
+fn parse_category(line: &str, token: &str) -> Result<char> {
+    match token {
+        "x" | "m" | "a" | "s" => Ok(token.chars().next().unwrap()),
+        _ => Err(AocError::parse(
+            1,
+            column_of(line, token),
+            format!("invalid category \"{}\"", token),
+        )),
+    }
+}
 
 impl std::str::FromStr for Part {
-    type Err = ParsePartError;
+    type Err = AocError;
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
+    fn from_str(s: &str) -> Result<Self> {
         let mut map = HashMap::new();
-        s.trim_matches(|c| c == '{' || c == '}')
-            .split(',')
-            .for_each(|s| {
-                let (c, n) = s.split_once('=').unwrap();
-                map.insert(c.chars().next().unwrap(), n.parse::<usize>().unwrap());
-            });
+        for rating in s.trim_matches(|c| c == '{' || c == '}').split(',') {
+            let (c, n) = split_token(s, rating, "=")?;
+            map.insert(parse_category(s, c)?, parse_token(s, n, "rating")?);
+        }
         Ok(Self(map))
     }
 }
@@ -57,32 +66,34 @@ struct Condition {
     action: String,
 }
 
-#[derive(Debug, Eq, PartialEq)]
-struct ParseConditionError;
-
-impl std::str::FromStr for Condition {
-    type Err = ParseConditionError;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        if let Some((f, action)) = s.split_once(':') {
-            let mut f_it = f.chars();
-            let key = f_it.next().unwrap();
-            let operator = f_it.next().unwrap();
-            let value = f_it.as_str().parse::<usize>().unwrap();
-            Ok(Self {
-                key: Some(key),
-                operator: Some(operator),
-                value: Some(value),
-                action: action.to_string(),
-            })
-        } else {
-            Ok(Self {
-                key: None,
-                operator: None,
-                value: None,
-                action: s.to_string(),
-            })
+// Conditions sit in the middle of a workflow line, so errors are reported against the whole line.
+fn parse_condition(line: &str, s: &str) -> Result<Condition> {
+    if let Some((f, action)) = s.split_once(':') {
+        let (key, rest) = f.split_at(f.len().min(1));
+        let key = parse_category(line, key)?;
+        let (operator, value) = rest.split_at(rest.len().min(1));
+        if operator != "<" && operator != ">" {
+            return Err(AocError::parse(
+                1,
+                column_of(line, rest),
+                format!("invalid operator \"{}\"", operator),
+            ));
         }
+        let operator = operator.chars().next();
+        let value = parse_token(line, value, "value")?;
+        Ok(Condition {
+            key: Some(key),
+            operator,
+            value: Some(value),
+            action: action.to_string(),
+        })
+    } else {
+        Ok(Condition {
+            key: None,
+            operator: None,
+            value: None,
+            action: s.to_string(),
+        })
     }
 }
 
@@ -114,7 +125,10 @@ impl Condition {
         }
     }
 
-    fn run_part_range(&self, input: &PartRange) -> Result<Option<String>, Vec<PartRange>> {
+    fn run_part_range(
+        &self,
+        input: &PartRange,
+    ) -> std::result::Result<Option<String>, Vec<PartRange>> {
         if let Some(key) = self.key {
             let op = self.operator.unwrap();
             let value = self.value.unwrap();
@@ -166,20 +180,24 @@ struct Workflow {
     conditions: Vec<Condition>,
 }
 
-#[derive(Debug, Eq, PartialEq)]
-struct ParseWorkflowError;
-
 impl std::str::FromStr for Workflow {
-    type Err = ParseWorkflowError;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (name, conditions_str) = s.split_once('{').unwrap();
+    type Err = AocError;
 
-        let conditions = conditions_str
+    fn from_str(s: &str) -> Result<Self> {
+        let (name, conditions_str) = split_token(s, s, "{")?;
+        let conditions: Vec<Condition> = conditions_str
             .trim_end_matches('}')
             .split(',')
-            .map(|s| s.parse::<Condition>().unwrap())
-            .collect();
+            .map(|c| parse_condition(s, c))
+            .collect::<Result<_>>()?;
+        // Without a fallback a part could match nothing and never leave the workflow.
+        if conditions.last().is_none_or(|c| c.key.is_some()) {
+            return Err(AocError::parse(
+                1,
+                s.len(),
+                "workflow has no fallback action",
+            ));
+        }
         Ok(Self {
             name: name.to_string(),
             conditions,
@@ -188,29 +206,46 @@ impl std::str::FromStr for Workflow {
 }
 
 #[derive(Debug, Clone)]
-struct WorkflowManager {
+pub struct WorkflowManager {
     workflows: HashMap<String, Workflow>,
     parts: Vec<Part>,
 }
 
-#[derive(Debug, Eq, PartialEq)]
-struct ParseWorkflowManagerError;
-
 impl std::str::FromStr for WorkflowManager {
-    type Err = ParseWorkflowManagerError;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (wf_str, parts_str) = s.split_once("\n\n").unwrap();
-        let mut workflows = HashMap::new();
-        wf_str.lines().for_each(|l| {
-            let workflow = l.parse::<Workflow>().unwrap();
-            workflows.insert(workflow.name.clone(), workflow);
-        });
-        let parts = parts_str
-            .lines()
-            .map(|l| l.parse::<Part>().unwrap())
+    type Err = AocError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let (wf_str, parts_str) = s.split_once("\n\n").ok_or_else(|| {
+            AocError::parse(
+                s.lines().count() + 1,
+                1,
+                "expected a blank line between workflows and parts",
+            )
+        })?;
+        let workflow_list: Vec<Workflow> = parse_lines(wf_str, str::parse)?;
+        // Sorting parts trusts that every action leads somewhere.
+        for (idx, (workflow, l)) in workflow_list.iter().zip(wf_str.lines()).enumerate() {
+            for condition in &workflow.conditions {
+                let action = condition.action.as_str();
+                if action != "A" && action != "R" && !workflow_list.iter().any(|w| w.name == action)
+                {
+                    return Err(AocError::parse(
+                        idx + 1,
+                        l.rfind(action).map_or(1, |c| c + 1),
+                        format!("unknown workflow \"{}\"", action),
+                    ));
+                }
+            }
+        }
+        if !workflow_list.iter().any(|w| w.name == "in") {
+            return Err(AocError::parse(1, 1, "there is no \"in\" workflow"));
+        }
+        let workflows = workflow_list
+            .into_iter()
+            .map(|w| (w.name.clone(), w))
             .collect();
-
+        let parts =
+            parse_lines(parts_str, str::parse).map_err(|e| e.below(wf_str.lines().count() + 1))?;
         Ok(Self { workflows, parts })
     }
 }
@@ -289,20 +324,40 @@ impl WorkflowManager {
     }
 }
 
-pub fn solution_day_19_01(file_path: String) -> Option<usize> {
-    let manager = fs::read_to_string(file_path)
-        .expect("Invalid Input File.")
-        .parse::<WorkflowManager>()
-        .unwrap();
-    Some(manager.get_total_accepted_value())
+pub struct Day19;
+
+impl Solution for Day19 {
+    const DAY: u8 = 19;
+    type Input = WorkflowManager;
+    type Output = usize;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        input.parse()
+    }
+
+    fn part1(manager: &Self::Input) -> Result<usize> {
+        Ok(manager.get_total_accepted_value())
+    }
+
+    fn part2(manager: &Self::Input) -> Result<usize> {
+        Ok(manager.find_combinations())
+    }
+}
+
+pub fn solution_day_19_01(file_path: String) -> Result<usize> {
+    solution_day_19_01_from_str(&read_input(file_path)?)
+}
+
+pub fn solution_day_19_01_from_str(input: &str) -> Result<usize> {
+    Day19::solve_part1(input)
+}
+
+pub fn solution_day_19_02(file_path: String) -> Result<usize> {
+    solution_day_19_02_from_str(&read_input(file_path)?)
 }
 
-pub fn solution_day_19_02(file_path: String) -> Option<usize> {
-    let manager = fs::read_to_string(file_path)
-        .expect("Invalid Input File.")
-        .parse::<WorkflowManager>()
-        .unwrap();
-    Some(manager.find_combinations())
+pub fn solution_day_19_02_from_str(input: &str) -> Result<usize> {
+    Day19::solve_part2(input)
 }
 
 #[cfg(test)]
@@ -1,12 +1,12 @@
 // Advent of Code 2023 - Day 20
 
-use std::{
-    collections::{HashMap, VecDeque},
-    fs,
-};
+use std::collections::{HashMap, VecDeque};
 
 use derive_deref::{Deref, DerefMut};
 
+use super::{read_input, Param, Params, Solution};
+use crate::error::{column_of, split_token, AocError, Result};
+
 #[derive(Debug, PartialEq, Eq, Clone, Hash)]
 struct Signal {
     name: String,
@@ -22,7 +22,7 @@ enum ModuleType {
 }
 
 #[derive(Debug, PartialEq, Eq, Clone)]
-struct Module {
+pub struct Module {
     name: String,
     module_type: ModuleType,
     destinations: Vec<String>,
@@ -78,21 +78,24 @@ impl Module {
     }
 }
 
-#[derive(Debug, PartialEq, Eq)]
-struct ParseModuleError;
-
 impl std::str::FromStr for Module {
-    type Err = ParseModuleError;
+    type Err = AocError;
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (src, dest) = s.split_once(" -> ").unwrap();
+    fn from_str(s: &str) -> Result<Self> {
+        let (src, dest) = split_token(s, s, " -> ")?;
         let destinations: Vec<String> = dest.split(", ").map(|x| x.to_string()).collect();
-        let name_parts = src.split_at(1);
-        let (module_type, name) = match name_parts {
-            ("&", name) => (ModuleType::Conjunction(HashMap::new()), name),
-            ("%", name) => (ModuleType::FlipFlop(false), name),
-            ("b", _) => (ModuleType::Broadcaster, "broadcaster"),
-            _ => panic!("Invalid module type."),
+        let (module_type, name) = if let Some(name) = src.strip_prefix('&') {
+            (ModuleType::Conjunction(HashMap::new()), name)
+        } else if let Some(name) = src.strip_prefix('%') {
+            (ModuleType::FlipFlop(false), name)
+        } else if src == "broadcaster" {
+            (ModuleType::Broadcaster, src)
+        } else {
+            return Err(AocError::parse(
+                1,
+                column_of(s, src),
+                format!("invalid module \"{}\"", src),
+            ));
         };
         Ok(Module {
             name: name.to_string(),
@@ -102,16 +105,13 @@ impl std::str::FromStr for Module {
     }
 }
 
-#[derive(Deref, DerefMut)]
-struct Circuit(HashMap<String, Module>);
-
-#[derive(Debug, PartialEq, Eq)]
-struct ParseCircuitError;
+#[derive(Clone, Deref, DerefMut)]
+pub struct Circuit(HashMap<String, Module>);
 
 impl std::str::FromStr for Circuit {
-    type Err = ParseCircuitError;
+    type Err = AocError;
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
+    fn from_str(s: &str) -> Result<Self> {
         let mut circuit = Self(HashMap::from([(
             "button".to_string(),
             Module {
@@ -120,11 +120,11 @@ impl std::str::FromStr for Circuit {
                 destinations: vec!["broadcaster".to_string()],
             },
         )]));
-        let mut instructions: Vec<&str> = s.lines().collect();
-        instructions.sort();
+        let mut instructions: Vec<(usize, &str)> = s.lines().enumerate().collect();
+        instructions.sort_by_key(|(_, ins)| *ins);
         instructions.reverse();
-        while let Some(ins) = instructions.pop() {
-            let mut module = ins.parse::<Module>().unwrap();
+        while let Some((idx, ins)) = instructions.pop() {
+            let mut module = ins.parse::<Module>().map_err(|e| e.below(idx))?;
             module.wire_up(&circuit);
             circuit.insert(module.name.clone(), module);
         }
@@ -175,13 +175,22 @@ impl Circuit {
         high * low
     }
 
-    fn run_until_on(&mut self) -> usize {
-        let rx_setter = self.values().find(|m| m.dest_contains("rx")).unwrap();
+    fn run_until_on(&mut self) -> Result<usize> {
+        let rx_setter = self
+            .values()
+            .find(|m| m.dest_contains("rx"))
+            .ok_or_else(|| AocError::solve("no module sends pulses to \"rx\""))?;
         let leading_to_rx_setter = self
             .values()
             .filter(|m| m.dest_contains(&rx_setter.name))
             .map(|m| m.name.clone())
             .collect::<Vec<String>>();
+        if leading_to_rx_setter.is_empty() {
+            return Err(AocError::solve(format!(
+                "no module sends pulses to \"{}\"",
+                rx_setter.name
+            )));
+        }
         let mut cycles = HashMap::new();
         let mut cycle = 0;
         'mloop: loop {
@@ -220,27 +229,51 @@ impl Circuit {
             }
         }
 
-        cycles.values().product()
+        Ok(cycles.values().product())
+    }
+}
+
+pub struct Day20;
+
+impl Solution for Day20 {
+    const DAY: u8 = 20;
+    const PARAMS: &'static [Param] = &[Param::new("presses", Some(1000), None)];
+    type Input = Circuit;
+    type Output = usize;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        input.parse()
+    }
+
+    fn part1(circuit: &Self::Input) -> Result<usize> {
+        Self::part1_with(circuit, &Params::default())
+    }
+
+    fn part1_with(circuit: &Self::Input, params: &Params) -> Result<usize> {
+        let mut circuit = circuit.clone();
+        Ok(circuit.total_pulse(params.get::<Self>("presses", 1)))
+    }
+
+    fn part2(circuit: &Self::Input) -> Result<usize> {
+        let mut circuit = circuit.clone();
+        circuit.run_until_on()
     }
 }
 
-pub fn solution_day_20_01(file_path: String) -> Option<usize> {
-    let mut circuit = fs::read_to_string(file_path)
-        .expect("Invalid input file.")
-        .parse::<Circuit>()
-        .unwrap();
+pub fn solution_day_20_01(file_path: String) -> Result<usize> {
+    solution_day_20_01_from_str(&read_input(file_path)?)
+}
 
-    Some(circuit.total_pulse(1000))
-    // Some((0..1000).fold(0, |acc, _| acc + circuit.run()))
+pub fn solution_day_20_01_from_str(input: &str) -> Result<usize> {
+    Day20::solve_part1(input)
 }
 
-pub fn solution_day_20_02(file_path: String) -> Option<usize> {
-    let mut circuit = fs::read_to_string(file_path)
-        .expect("Invalid input file.")
-        .parse::<Circuit>()
-        .unwrap();
+pub fn solution_day_20_02(file_path: String) -> Result<usize> {
+    solution_day_20_02_from_str(&read_input(file_path)?)
+}
 
-    Some(circuit.run_until_on())
+pub fn solution_day_20_02_from_str(input: &str) -> Result<usize> {
+    Day20::solve_part2(input)
 }
 
 #[cfg(test)]
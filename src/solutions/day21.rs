@@ -1,64 +1,44 @@
 // Advent of Code 2023 - Day 21
-use std::{
-    backtrace,
-    collections::{HashSet, VecDeque},
-};
+use std::collections::HashSet;
 
-use nalgebra::{Matrix3, Matrix3x1};
+use super::{read_input, Param, Params, Solution};
+use crate::error::{AocError, Result};
+use crate::utils::collections::{Faux2DArray, NEIGHBORS_4};
 
 #[derive(Debug, Clone)]
-struct GardenMap {
-    map: Vec<Vec<bool>>,
+pub struct GardenMap {
+    map: Faux2DArray<bool>,
     start: (usize, usize),
     height: usize,
     width: usize,
 }
 
-#[derive(Debug, PartialEq, Eq)]
-struct ParseGardenMapError;
-
 impl std::str::FromStr for GardenMap {
-    type Err = ParseGardenMapError;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut map = Vec::new();
-        let mut start = (0, 0);
-        for (y, line) in s.lines().enumerate() {
-            let mut row = Vec::new();
-            for (x, c) in line.chars().enumerate() {
-                row.push(c != '#');
-                if c == 'S' {
-                    start = (x, y);
-                }
-            }
-            map.push(row);
-        }
+    type Err = AocError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let tiles = Faux2DArray::from_text(s, |c| ".#S".contains(c).then_some(c))?;
+        let (start, _) = tiles
+            .positions()
+            .find(|(_, c)| **c == 'S')
+            .ok_or_else(|| AocError::parse(1, 1, "no starting plot 'S'"))?;
         Ok(GardenMap {
-            map: map.clone(),
+            map: tiles.map(|c| *c != '#'),
             start,
-            height: map.len(),
-            width: map[0].len(),
+            height: tiles.height(),
+            width: tiles.width,
         })
     }
 }
 
 impl GardenMap {
     fn find_possible_moves(&self, pos: (isize, isize)) -> Vec<(isize, isize)> {
-        let mut moves = Vec::new();
         let (x, y) = pos;
-        if let Some(true) = self.at((x - 1, y)) {
-            moves.push((x - 1, y));
-        }
-        if let Some(true) = self.at((x + 1, y)) {
-            moves.push((x + 1, y));
-        }
-        if let Some(true) = self.at((x, y - 1)) {
-            moves.push((x, y - 1));
-        }
-        if let Some(true) = self.at((x, y + 1)) {
-            moves.push((x, y + 1));
-        }
-        moves
+        NEIGHBORS_4
+            .iter()
+            .map(|(dx, dy)| (x + dx, y + dy))
+            .filter(|next| self.at(*next))
+            .collect()
     }
 
     fn round(&self, pos: (isize, isize)) -> (isize, isize) {
@@ -69,9 +49,10 @@ impl GardenMap {
         )
     }
 
-    fn at(&self, pos: (isize, isize)) -> Option<bool> {
+    // The garden repeats in every direction.
+    fn at(&self, pos: (isize, isize)) -> bool {
         let (x, y) = self.round(pos);
-        self.map[y as usize][x as usize].into()
+        self.map[(x as usize, y as usize)]
     }
 
     fn possible_move_after_steps(&self, steps: usize) -> HashSet<(isize, isize)> {
@@ -85,47 +66,116 @@ impl GardenMap {
         moves
     }
 
-    // fn possible_move_after_steps_in_infinite_wrap(&self, steps: usize) -> f64 {
-    //     let b = Matrix3x1::from_iterator((0..3).map(|i| {
-    //         self.possible_move_after_steps((i * self.width) + steps % self.width)
-    //             .len() as f64
-    //     }));
-    //     let a: Matrix3<f64> = Matrix3::from([[0., 0., 1.], [1., 1., 1.], [4., 2., 1.]]);
-    //     let decomp = a.lu();
-    //     let x = decomp.solve(&b).unwrap();
-    //     println!("{:?}", x);
-    //     x[0].abs() * (steps as f64).powi(2) + x[1].abs() * steps as f64 + x[2].abs()
-    // }
+    // Exact for any number of steps, but slow for many: each plot is visited once, and the plots
+    // reached after an even number of steps stay reachable after every even number of steps.
+    fn reachable_plots(&self, steps: usize) -> usize {
+        let start = (self.start.0 as isize, self.start.1 as isize);
+        let mut seen = HashSet::from([start]);
+        let mut frontier = vec![start];
+        let mut count = usize::from(steps.is_multiple_of(2));
+        for step in 1..=steps {
+            frontier = frontier
+                .iter()
+                .flat_map(|p| self.find_possible_moves(*p))
+                .filter(|p| seen.insert(*p))
+                .collect();
+            if step % 2 == steps % 2 {
+                count += frontier.len();
+            }
+        }
+        count
+    }
+
+    // The count only grows quadratically every `height` steps when the garden is square, the walk
+    // starts in its middle along an empty row and column, and ends half way across a garden.
+    fn can_extrapolate(&self, steps: usize) -> bool {
+        let (x, y) = self.start;
+        self.width == self.height
+            && (x, y) == (self.width / 2, self.height / 2)
+            && steps % self.height == self.height / 2
+            && (0..self.width).all(|i| self.map[(i, y)])
+            && (0..self.height).all(|i| self.map[(x, i)])
+    }
 
-    fn possible_move_after_steps_in_infinite_wrap(&self, steps: usize) -> usize {
+    fn possible_move_after_steps_in_infinite_wrap(&self, steps: usize) -> Result<usize> {
+        if !self.can_extrapolate(steps) {
+            if steps > MAX_WALK {
+                return Err(AocError::solve(format!(
+                    "{} steps can't be extrapolated on this garden, and are too many to walk",
+                    steps
+                )));
+            }
+            return Ok(self.reachable_plots(steps));
+        }
         let mut history = vec![];
         for c in 1..=steps {
             if c % self.height == self.height / 2 {
                 history.push(self.possible_move_after_steps(c).len());
                 if let &[y0, y1, y2] = &history[..] {
                     let x = steps / self.height;
-                    return (x * x * (y0 + y2 - 2 * y1) + x * (4 * y1 - 3 * y0 - y2) + 2 * y0) / 2;
+                    return Ok(
+                        (x * x * (y0 + y2 - 2 * y1) + x * (4 * y1 - 3 * y0 - y2) + 2 * y0) / 2,
+                    );
                 }
             }
         }
-        0
+        // Too few steps to fit the curve, but then few enough to walk them all.
+        Ok(self.reachable_plots(steps))
+    }
+}
+
+// Walking takes time in proportion to the square of the steps, this many take about a second.
+const MAX_WALK: usize = 1000;
+
+pub struct Day21;
+
+impl Solution for Day21 {
+    const DAY: u8 = 21;
+    const PARAMS: &'static [Param] = &[Param::new("steps", Some(64), Some(26501365))];
+    type Input = GardenMap;
+    type Output = usize;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        input.parse()
+    }
+
+    fn part1(map: &Self::Input) -> Result<usize> {
+        Self::part1_with(map, &Params::default())
+    }
+
+    fn part2(map: &Self::Input) -> Result<usize> {
+        Self::part2_with(map, &Params::default())
+    }
+
+    fn part1_with(map: &Self::Input, params: &Params) -> Result<usize> {
+        Ok(map
+            .possible_move_after_steps(params.get::<Self>("steps", 1))
+            .len())
+    }
+
+    fn part2_with(map: &Self::Input, params: &Params) -> Result<usize> {
+        map.possible_move_after_steps_in_infinite_wrap(params.get::<Self>("steps", 2))
     }
 }
 
-pub fn solution_day_21_01(file_path: String, moves: usize) -> Option<usize> {
-    let map = std::fs::read_to_string(file_path)
-        .unwrap()
-        .parse::<GardenMap>()
-        .unwrap();
-    Some(map.possible_move_after_steps(moves).len())
+pub fn solution_day_21_01(file_path: String, moves: usize) -> Result<usize> {
+    solution_day_21_01_from_str(&read_input(file_path)?, moves)
+}
+
+pub fn solution_day_21_01_from_str(input: &str, moves: usize) -> Result<usize> {
+    Day21::parse(input)
+        .map(|map| map.possible_move_after_steps(moves).len())
+        .map_err(|e| e.in_day(Day21::DAY))
 }
 
-pub fn solution_day_21_02(file_path: String, moves: usize) -> Option<usize> {
-    let map = std::fs::read_to_string(file_path)
-        .unwrap()
-        .parse::<GardenMap>()
-        .unwrap();
-    Some(map.possible_move_after_steps_in_infinite_wrap(moves))
+pub fn solution_day_21_02(file_path: String, moves: usize) -> Result<usize> {
+    solution_day_21_02_from_str(&read_input(file_path)?, moves)
+}
+
+pub fn solution_day_21_02_from_str(input: &str, moves: usize) -> Result<usize> {
+    Day21::parse(input)
+        .and_then(|map| map.possible_move_after_steps_in_infinite_wrap(moves))
+        .map_err(|e| e.in_day(Day21::DAY))
 }
 
 #[cfg(test)]
@@ -142,8 +192,18 @@ mod tests {
     #[test]
     fn test_day_21_02() {
         let file_path: String = String::from("src/inputs/day21e.txt");
-        let result = solution_day_21_02(file_path, 26501365).unwrap();
-        assert_eq!(result, 528192899606863);
+        for (steps, expected) in [(10, 50), (50, 1594), (100, 6536), (500, 167004)] {
+            let result = solution_day_21_02(file_path.clone(), steps).unwrap();
+            assert_eq!(result, expected, "{} steps", steps);
+        }
+        // The example has no empty row and column through the start to extrapolate along.
+        assert_eq!(
+            solution_day_21_02(file_path, 26501365),
+            Err(AocError::solve(
+                "26501365 steps can't be extrapolated on this garden, and are too many to walk"
+            )
+            .in_day(21))
+        );
     }
 
     #[test]
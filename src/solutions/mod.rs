@@ -1,3 +1,7 @@
+use std::{any::Any, collections::HashMap, fmt, fs};
+
+use crate::error::{AocError, Result};
+
 pub mod day01;
 pub mod day02;
 pub mod day03;
@@ -19,3 +23,387 @@ pub mod day18;
 pub mod day19;
 pub mod day20;
 pub mod day21;
+
+// Every day returns its own integer type, this is what they all turn into.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Answer {
+    Number(i128),
+    Text(String),
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Answer::Number(v) => write!(f, "{}", v),
+            Answer::Text(v) => write!(f, "{}", v),
+        }
+    }
+}
+
+impl From<u32> for Answer {
+    fn from(value: u32) -> Self {
+        Answer::Number(value.into())
+    }
+}
+
+impl From<usize> for Answer {
+    fn from(value: usize) -> Self {
+        Answer::Number(value as i128)
+    }
+}
+
+impl From<isize> for Answer {
+    fn from(value: isize) -> Self {
+        Answer::Number(value as i128)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(value: String) -> Self {
+        Answer::Text(value)
+    }
+}
+
+// A named number a day can be tuned with, and what each part uses when it isn't given.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Param {
+    pub name: &'static str,
+    pub part1: Option<usize>,
+    pub part2: Option<usize>,
+}
+
+impl Param {
+    pub const fn new(name: &'static str, part1: Option<usize>, part2: Option<usize>) -> Self {
+        Self { name, part1, part2 }
+    }
+
+    pub fn default_for(&self, part: u8) -> Option<usize> {
+        match part {
+            1 => self.part1,
+            2 => self.part2,
+            _ => None,
+        }
+    }
+}
+
+// Parameter values given on the command line.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Params(HashMap<String, usize>);
+
+impl Params {
+    pub fn set(&mut self, name: &str, value: usize) {
+        self.0.insert(name.to_string(), value);
+    }
+
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.0.keys().map(|k| k.as_str())
+    }
+
+    pub fn get<S: Solution + ?Sized>(&self, name: &str, part: u8) -> usize {
+        if let Some(v) = self.0.get(name) {
+            return *v;
+        }
+        S::PARAMS
+            .iter()
+            .find(|p| p.name == name)
+            .and_then(|p| p.default_for(part))
+            .expect("Parameter without a default for this part.")
+    }
+}
+
+// Each day parses its input once, and both parts work on the parsed value.
+pub trait Solution {
+    const DAY: u8;
+    const PARAMS: &'static [Param] = &[];
+    type Input: 'static;
+    type Output: Into<Answer>;
+
+    fn parse(input: &str) -> Result<Self::Input>;
+    fn part1(input: &Self::Input) -> Result<Self::Output>;
+    fn part2(input: &Self::Input) -> Result<Self::Output>;
+
+    // Days with parameters override these, the plain parts then use the defaults.
+    fn part1_with(input: &Self::Input, _params: &Params) -> Result<Self::Output> {
+        Self::part1(input)
+    }
+
+    fn part2_with(input: &Self::Input, _params: &Params) -> Result<Self::Output> {
+        Self::part2(input)
+    }
+
+    fn solve_part1(input: &str) -> Result<Self::Output> {
+        Self::parse(input)
+            .and_then(|i| Self::part1(&i))
+            .map_err(|e| e.in_day(Self::DAY))
+    }
+
+    fn solve_part2(input: &str) -> Result<Self::Output> {
+        Self::parse(input)
+            .and_then(|i| Self::part2(&i))
+            .map_err(|e| e.in_day(Self::DAY))
+    }
+}
+
+pub fn read_input(file_path: String) -> Result<String> {
+    fs::read_to_string(&file_path).map_err(|e| AocError::io(&file_path, e))
+}
+
+// The registry can't know every day's input type, so parsed inputs are passed around as `Any`.
+pub type ParsedInput = Box<dyn Any>;
+
+fn parse_any<S: Solution>(input: &str) -> Result<ParsedInput> {
+    match S::parse(input) {
+        Ok(parsed) => Ok(Box::new(parsed)),
+        Err(e) => Err(e.in_day(S::DAY)),
+    }
+}
+
+fn part1_any<S: Solution>(input: &dyn Any, params: &Params) -> Result<Answer> {
+    let input = input
+        .downcast_ref::<S::Input>()
+        .expect("Input parsed by another day.");
+    S::part1_with(input, params)
+        .map(Into::into)
+        .map_err(|e| e.in_day(S::DAY))
+}
+
+fn part2_any<S: Solution>(input: &dyn Any, params: &Params) -> Result<Answer> {
+    let input = input
+        .downcast_ref::<S::Input>()
+        .expect("Input parsed by another day.");
+    S::part2_with(input, params)
+        .map(Into::into)
+        .map_err(|e| e.in_day(S::DAY))
+}
+
+pub struct Puzzle {
+    pub day: u8,
+    pub part: u8,
+    pub params: &'static [Param],
+    pub parser: fn(&str) -> Result<ParsedInput>,
+    pub solver: fn(&dyn Any, &Params) -> Result<Answer>,
+}
+
+impl Puzzle {
+    pub const fn part1<S: Solution>() -> Self {
+        Self {
+            day: S::DAY,
+            part: 1,
+            params: S::PARAMS,
+            parser: parse_any::<S>,
+            solver: part1_any::<S>,
+        }
+    }
+
+    pub const fn part2<S: Solution>() -> Self {
+        Self {
+            day: S::DAY,
+            part: 2,
+            params: S::PARAMS,
+            parser: parse_any::<S>,
+            solver: part2_any::<S>,
+        }
+    }
+
+    // The parameters this part actually reads.
+    pub fn used_params(&self) -> impl Iterator<Item = &'static Param> + '_ {
+        self.params
+            .iter()
+            .filter(|p| p.default_for(self.part).is_some())
+    }
+
+    pub fn parse(&self, input: &str) -> Result<ParsedInput> {
+        (self.parser)(input)
+    }
+
+    pub fn solve(&self, input: &dyn Any) -> Result<Answer> {
+        self.solve_with(input, &Params::default())
+    }
+
+    pub fn solve_with(&self, input: &dyn Any, params: &Params) -> Result<Answer> {
+        (self.solver)(input, params)
+    }
+
+    pub fn run(&self, file_path: String) -> Result<Answer> {
+        self.run_with(file_path, &Params::default())
+    }
+
+    pub fn run_with(&self, file_path: String, params: &Params) -> Result<Answer> {
+        let input = read_input(file_path)?;
+        self.solve_with(self.parse(&input)?.as_ref(), params)
+    }
+}
+
+pub const PUZZLES: &[Puzzle] = &[
+    Puzzle::part1::<day01::Day01>(),
+    Puzzle::part2::<day01::Day01>(),
+    Puzzle::part1::<day02::Day02>(),
+    Puzzle::part2::<day02::Day02>(),
+    Puzzle::part1::<day03::Day03>(),
+    Puzzle::part2::<day03::Day03>(),
+    Puzzle::part1::<day04::Day04>(),
+    Puzzle::part2::<day04::Day04>(),
+    Puzzle::part1::<day05::Day05>(),
+    Puzzle::part2::<day05::Day05>(),
+    Puzzle::part1::<day06::Day06>(),
+    Puzzle::part2::<day06::Day06>(),
+    Puzzle::part1::<day07::Day07>(),
+    Puzzle::part2::<day07::Day07>(),
+    Puzzle::part1::<day08::Day08>(),
+    Puzzle::part2::<day08::Day08>(),
+    Puzzle::part1::<day09::Day09>(),
+    Puzzle::part2::<day09::Day09>(),
+    Puzzle::part1::<day10::Day10>(),
+    Puzzle::part2::<day10::Day10>(),
+    Puzzle::part1::<day11::Day11>(),
+    Puzzle::part2::<day11::Day11>(),
+    Puzzle::part1::<day12::Day12>(),
+    Puzzle::part2::<day12::Day12>(),
+    Puzzle::part1::<day13::Day13>(),
+    Puzzle::part2::<day13::Day13>(),
+    Puzzle::part1::<day14::Day14>(),
+    Puzzle::part2::<day14::Day14>(),
+    Puzzle::part1::<day15::Day15>(),
+    Puzzle::part2::<day15::Day15>(),
+    Puzzle::part1::<day16::Day16>(),
+    Puzzle::part2::<day16::Day16>(),
+    Puzzle::part1::<day17::Day17>(),
+    Puzzle::part2::<day17::Day17>(),
+    Puzzle::part1::<day18::Day18>(),
+    Puzzle::part2::<day18::Day18>(),
+    Puzzle::part1::<day19::Day19>(),
+    Puzzle::part2::<day19::Day19>(),
+    Puzzle::part1::<day20::Day20>(),
+    Puzzle::part2::<day20::Day20>(),
+    Puzzle::part1::<day21::Day21>(),
+    Puzzle::part2::<day21::Day21>(),
+];
+
+pub const DEFAULT_EVENT: &str = "2023";
+pub const DEFAULT_ROOT: &str = "src";
+
+// A set of puzzles, like one year of Advent of Code. Apart from the default one, each event
+// keeps its modules and inputs in its own directory under `solutions` and `inputs`.
+pub struct Event {
+    pub name: &'static str,
+    pub dir: &'static str,
+    pub puzzles: &'static [Puzzle],
+}
+
+impl Event {
+    pub const fn new(name: &'static str, dir: &'static str, puzzles: &'static [Puzzle]) -> Self {
+        Self { name, dir, puzzles }
+    }
+
+    pub fn solutions_dir(&self, root: &str) -> String {
+        match self.dir {
+            "" => format!("{}/solutions", root),
+            dir => format!("{}/solutions/{}", root, dir),
+        }
+    }
+
+    pub fn inputs_dir(&self, root: &str) -> String {
+        match self.dir {
+            "" => format!("{}/inputs", root),
+            dir => format!("{}/inputs/{}", root, dir),
+        }
+    }
+
+    pub fn input_path(&self, root: &str, day: u8) -> String {
+        format!("{}/day{:02}.txt", self.inputs_dir(root), day)
+    }
+
+    pub fn example_path(&self, root: &str, day: u8, suffix: &str) -> String {
+        format!("{}/day{:02}e{}.txt", self.inputs_dir(root), day, suffix)
+    }
+
+    pub fn find_puzzle(&self, day: u8, part: u8) -> Option<&'static Puzzle> {
+        self.puzzles.iter().find(|p| p.day == day && p.part == part)
+    }
+
+    pub fn puzzles_of_day(&self, day: u8) -> impl Iterator<Item = &'static Puzzle> {
+        self.puzzles.iter().filter(move |p| p.day == day)
+    }
+}
+
+pub const EVENTS: &[Event] = &[Event::new(DEFAULT_EVENT, "", PUZZLES)];
+
+pub fn find_event(name: &str) -> Option<&'static Event> {
+    EVENTS.iter().find(|e| e.name == name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event() -> &'static Event {
+        find_event(DEFAULT_EVENT).unwrap()
+    }
+
+    #[test]
+    fn test_puzzles_sorted_and_unique() {
+        for pair in PUZZLES.windows(2) {
+            assert!((pair[0].day, pair[0].part) < (pair[1].day, pair[1].part));
+        }
+    }
+
+    #[test]
+    fn test_find_puzzle() {
+        let puzzle = event().find_puzzle(2, 1).unwrap();
+        assert_eq!(
+            puzzle.run(String::from("src/inputs/day02e.txt")),
+            Ok(Answer::Number(8))
+        );
+        assert!(event().find_puzzle(2, 3).is_none());
+        assert_eq!(event().puzzles_of_day(11).count(), 2);
+    }
+
+    #[test]
+    fn test_solutions_from_str() {
+        let result = day02::solution_day_02_02_from_str(include_str!("../inputs/day02e.txt"));
+        assert_eq!(result, Ok(2286));
+        let result = day06::solution_day_06_01_from_str("Time: 7 15 30\nDistance: 9 40 200");
+        assert_eq!(result, Ok(288));
+    }
+
+    #[test]
+    fn test_events() {
+        assert_eq!(event().puzzles.len(), PUZZLES.len());
+        assert_eq!(event().input_path("src", 2), "src/inputs/day02.txt");
+        assert_eq!(
+            Event::new("2024", "y2024", &[]).example_path("src", 1, "2"),
+            "src/inputs/y2024/day01e2.txt"
+        );
+        assert!(find_event("1999").is_none());
+    }
+
+    #[test]
+    fn test_params() {
+        let puzzle = event().find_puzzle(11, 2).unwrap();
+        let input = puzzle.parse(include_str!("../inputs/day11e.txt")).unwrap();
+        let mut params = Params::default();
+        params.set("multiplier", 100);
+        assert_eq!(
+            puzzle.solve_with(input.as_ref(), &params),
+            Ok(Answer::Number(8410))
+        );
+        let names: Vec<&str> = event()
+            .find_puzzle(20, 2)
+            .unwrap()
+            .used_params()
+            .map(|p| p.name)
+            .collect();
+        assert!(names.is_empty());
+    }
+
+    #[test]
+    #[ignore]
+    fn output_all_puzzles() {
+        for event in EVENTS {
+            for puzzle in event.puzzles {
+                let input_path = event.input_path(DEFAULT_ROOT, puzzle.day);
+                assert!(puzzle.run(input_path).is_ok(), "{}", event.name);
+            }
+        }
+    }
+}
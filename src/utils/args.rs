@@ -0,0 +1,56 @@
+// Command line errors shared by the binaries, which all parse their arguments with pico-args.
+#[derive(Debug)]
+pub enum ArgsError {
+    Invalid(pico_args::Error),
+    Unrecognised(Vec<String>),
+    Conflict(&'static str, &'static str),
+}
+
+impl ArgsError {
+    // Nothing was given, so only the usage is worth printing.
+    pub fn is_missing(&self) -> bool {
+        matches!(self, ArgsError::Invalid(pico_args::Error::MissingArgument))
+    }
+}
+
+impl From<pico_args::Error> for ArgsError {
+    fn from(e: pico_args::Error) -> Self {
+        ArgsError::Invalid(e)
+    }
+}
+
+impl std::fmt::Display for ArgsError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            ArgsError::Invalid(e) => write!(f, "{}", e),
+            ArgsError::Unrecognised(args) => {
+                write!(f, "unrecognised arguments: {}", args.join(" "))
+            }
+            ArgsError::Conflict(a, b) => write!(f, "`{}` can't be used with `{}`", a, b),
+        }
+    }
+}
+
+// Call once everything known was taken out, a typo shouldn't fall back to a default.
+pub fn finish(args: pico_args::Arguments) -> Result<(), ArgsError> {
+    let rest = args.finish();
+    if rest.is_empty() {
+        return Ok(());
+    }
+    let rest = rest.iter().map(|a| a.to_string_lossy().to_string());
+    Err(ArgsError::Unrecognised(rest.collect()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_finish() {
+        let args = pico_args::Arguments::from_vec(vec!["--formt".into(), "csv".into()]);
+        let error = finish(args).unwrap_err();
+        assert_eq!(error.to_string(), "unrecognised arguments: --formt csv");
+        assert!(finish(pico_args::Arguments::from_vec(vec![])).is_ok());
+        assert!(ArgsError::from(pico_args::Error::MissingArgument).is_missing());
+    }
+}
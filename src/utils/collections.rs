@@ -1,19 +1,41 @@
-use std::fmt::{self, Debug};
-
+use std::{
+    fmt::{self, Debug, Display},
+    ops::{Index, IndexMut},
+    str::FromStr,
+};
+
+use crate::error::{AocError, Result};
+
+// Offsets to the cells sharing a side: up, right, down and left.
+pub const NEIGHBORS_4: [(isize, isize); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+
+// The same, followed by the cells sharing only a corner.
+pub const NEIGHBORS_8: [(isize, isize); 8] = [
+    (0, -1),
+    (1, 0),
+    (0, 1),
+    (-1, 0),
+    (1, -1),
+    (1, 1),
+    (-1, 1),
+    (-1, -1),
+];
+
+#[derive(Clone, PartialEq, Eq, Hash)]
 pub struct Faux2DArray<T> {
     pub width: usize,
     pub items: Vec<T>,
 }
 
-impl<T: Debug> fmt::Display for Faux2DArray<T> {
+// Every cell prints itself, so a grid of chars prints as the text it was parsed from.
+impl<T: Display> fmt::Display for Faux2DArray<T> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        // Write strictly the first element into the supplied output
-        // stream: `f`. Returns `fmt::Result` which indicates whether the
-        // operation succeeded or failed. Note that `write!` uses syntax which
-        // is very similar to `println!`.
-        self.items.chunks(self.width).for_each(|l| {
-            writeln!(f, "{:?}", l).expect("Cannot print line");
-        });
+        for row in self.items.chunks(self.width.max(1)) {
+            for item in row {
+                write!(f, "{}", item)?;
+            }
+            writeln!(f)?;
+        }
         Ok(())
     }
 }
@@ -24,13 +46,35 @@ impl<T: Debug> Debug for Faux2DArray<T> {
         // stream: `f`. Returns `fmt::Result` which indicates whether the
         // operation succeeded or failed. Note that `write!` uses syntax which
         // is very similar to `println!`.
-        self.items.chunks(self.width).for_each(|l| {
+        self.items.chunks(self.width.max(1)).for_each(|l| {
             writeln!(f, "{:?}", l).expect("Cannot print line");
         });
         Ok(())
     }
 }
 
+impl<T: TryFrom<char>> FromStr for Faux2DArray<T> {
+    type Err = AocError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Self::from_text(s, |c| T::try_from(c).ok())
+    }
+}
+
+impl<T> Index<(usize, usize)> for Faux2DArray<T> {
+    type Output = T;
+
+    fn index(&self, (x, y): (usize, usize)) -> &T {
+        self.at(x, y).expect("Position outside of the grid.")
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Faux2DArray<T> {
+    fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut T {
+        self.at_mut(x, y).expect("Position outside of the grid.")
+    }
+}
+
 impl<T> FromIterator<T> for Faux2DArray<T> {
     fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
         let mut s = Self {
@@ -50,6 +94,46 @@ impl<T> Faux2DArray<T> {
         }
     }
 
+    // One row per line, each as long as the first. `mapper` gives None for characters it doesn't know.
+    pub fn from_text<F>(text: &str, mut mapper: F) -> Result<Faux2DArray<T>>
+    where
+        F: FnMut(char) -> Option<T>,
+    {
+        let width = text.lines().next().map_or(0, |l| l.chars().count());
+        if width == 0 {
+            return Err(AocError::parse(1, 1, "empty grid"));
+        }
+        let mut items = Vec::with_capacity(width * text.lines().count());
+        for (y, line) in text.lines().enumerate() {
+            let mut length = 0;
+            for (x, c) in line.chars().enumerate() {
+                let item = mapper(c).ok_or_else(|| {
+                    AocError::parse(y + 1, x + 1, format!("invalid tile '{}'", c))
+                })?;
+                items.push(item);
+                length += 1;
+            }
+            if length != width {
+                return Err(AocError::parse(
+                    y + 1,
+                    1,
+                    format!("expected {} tiles", width),
+                ));
+            }
+        }
+        Ok(Self { items, width })
+    }
+
+    pub fn map<U, F>(&self, mapper: F) -> Faux2DArray<U>
+    where
+        F: FnMut(&T) -> U,
+    {
+        Faux2DArray {
+            items: self.items.iter().map(mapper).collect(),
+            width: self.width,
+        }
+    }
+
     pub fn absolute_index(&self, x: usize, y: usize) -> usize {
         (y * self.width) + x
     }
@@ -73,11 +157,19 @@ impl<T> Faux2DArray<T> {
         Self { items, width }
     }
 
+    // An array without columns has no rows either.
     pub fn height(&self) -> usize {
+        if self.width == 0 {
+            return 0;
+        }
         self.items.len() / self.width
     }
 
+    // An array without columns takes the width of its first row.
     pub fn add_row(&mut self, data: Vec<T>) -> std::result::Result<(), &'static str> {
+        if self.width == 0 {
+            self.width = data.len();
+        }
         if data.len() != self.width {
             return Err("Invalid row length");
         }
@@ -86,8 +178,8 @@ impl<T> Faux2DArray<T> {
     }
 
     pub fn remove_row(&mut self, row: usize) -> std::result::Result<(), &'static str> {
-        if row >= self.width {
-            return Err("Invalid row length");
+        if row >= self.height() {
+            return Err("Row out of range");
         }
         let start = row * self.width;
         let end = start + self.width;
@@ -95,7 +187,13 @@ impl<T> Faux2DArray<T> {
         Ok(())
     }
 
+    // An array without columns takes the height of its first column.
     pub fn add_col(&mut self, data: Vec<T>) -> std::result::Result<(), &'static str> {
+        if self.width == 0 {
+            self.items = data;
+            self.width = 1;
+            return Ok(());
+        }
         let data_length = data.len();
         if data_length != self.height() {
             return Err("Invalid column length");
@@ -109,14 +207,15 @@ impl<T> Faux2DArray<T> {
     }
 
     pub fn remove_col(&mut self, col: usize) -> std::result::Result<(), &'static str> {
-        if col >= self.height() {
-            return Err("Invalid column length");
-        }
-        for i in (0..self.height()).rev() {
-            let position = col + (self.width * i);
-            println!("{:?}", position);
-            self.items.remove(position);
+        if col >= self.width {
+            return Err("Column out of range");
         }
+        let width = self.width;
+        let mut idx = 0;
+        self.items.retain(|_| {
+            idx += 1;
+            (idx - 1) % width != col
+        });
         self.width -= 1;
         Ok(())
     }
@@ -128,6 +227,63 @@ impl<T> Faux2DArray<T> {
         Some(&self.items[self.absolute_index(x, y)])
     }
 
+    pub fn at_mut(&mut self, x: usize, y: usize) -> Option<&mut T> {
+        if x >= self.width || y >= self.height() {
+            return None;
+        }
+        let idx = self.absolute_index(x, y);
+        Some(&mut self.items[idx])
+    }
+
+    pub fn contains(&self, x: isize, y: isize) -> bool {
+        x >= 0 && y >= 0 && (x as usize) < self.width && (y as usize) < self.height()
+    }
+
+    // Like `at`, for positions that may have stepped off the grid on any side.
+    pub fn get(&self, x: isize, y: isize) -> Option<&T> {
+        if !self.contains(x, y) {
+            return None;
+        }
+        self.at(x as usize, y as usize)
+    }
+
+    pub fn get_mut(&mut self, x: isize, y: isize) -> Option<&mut T> {
+        if !self.contains(x, y) {
+            return None;
+        }
+        self.at_mut(x as usize, y as usize)
+    }
+
+    // Every cell along with its position, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = ((usize, usize), &T)> + '_ {
+        self.items
+            .iter()
+            .enumerate()
+            .map(|(idx, v)| (self.cartesian_index(idx), v))
+    }
+
+    fn neighbors<'a>(
+        &'a self,
+        x: usize,
+        y: usize,
+        offsets: &'static [(isize, isize)],
+    ) -> impl Iterator<Item = ((usize, usize), &'a T)> + 'a {
+        offsets.iter().filter_map(move |(dx, dy)| {
+            let (nx, ny) = (x as isize + dx, y as isize + dy);
+            self.get(nx, ny).map(|v| ((nx as usize, ny as usize), v))
+        })
+    }
+
+    // The cells sharing a side with (x, y), in the order of `NEIGHBORS_4`.
+    pub fn neighbors4(&self, x: usize, y: usize) -> impl Iterator<Item = ((usize, usize), &T)> {
+        self.neighbors(x, y, &NEIGHBORS_4)
+    }
+
+    // The cells sharing a side or a corner with (x, y), in the order of `NEIGHBORS_8`.
+    pub fn neighbors8(&self, x: usize, y: usize) -> impl Iterator<Item = ((usize, usize), &T)> {
+        self.neighbors(x, y, &NEIGHBORS_8)
+    }
+
     pub fn next_x(&self, x: usize, y: usize) -> Option<&T> {
         if x + 1 >= self.width {
             return None;
@@ -162,6 +318,9 @@ impl<T> Faux2DArray<T> {
     }
 
     pub fn row(&self, row: usize) -> Option<impl Iterator<Item = &T> + '_> {
+        if row >= self.height() {
+            return None;
+        }
         let start = self.width * row;
         let end = start + self.width;
         Some(self.items[start..end].iter())
@@ -176,7 +335,7 @@ impl<T> Faux2DArray<T> {
 
     pub fn rows(&self) -> impl Iterator<Item = Vec<&T>> + '_ {
         self.items
-            .chunks(self.width)
+            .chunks(self.width.max(1))
             .map(|c| c.iter().collect::<Vec<&T>>())
     }
 
@@ -346,6 +505,166 @@ mod tests {
         assert_eq!(a.items.len(), 20);
     }
 
+    #[test]
+    fn test_mutation_errors() {
+        let mut a = create_usize_array();
+        a.add_col(vec![8, 8, 8, 8, 8]).unwrap();
+        assert_eq!(a.remove_row(5), Err("Row out of range"));
+        assert_eq!(a.remove_col(6), Err("Column out of range"));
+        assert!(a.remove_row(4).is_ok() && a.remove_col(5).is_ok());
+        assert_eq!(a.add_row(vec![1]), Err("Invalid row length"));
+        assert_eq!(a.add_col(vec![1]), Err("Invalid column length"));
+        assert!(a.row(4).is_none());
+        assert_eq!(a.row(3).unwrap().count(), 5);
+
+        let mut empty: Faux2DArray<usize> = Faux2DArray::new(0);
+        assert_eq!(empty.height(), 0);
+        assert_eq!(empty.rows().count(), 0);
+        assert!(empty.row(0).is_none());
+        empty.add_col(vec![1, 2, 3]).unwrap();
+        assert_eq!((empty.width, empty.height()), (1, 3));
+        empty.remove_col(0).unwrap();
+        assert_eq!((empty.width, empty.height()), (0, 0));
+        empty.add_row(vec![4, 5]).unwrap();
+        assert_eq!((empty.width, empty.height()), (2, 1));
+    }
+
+    // The obvious representation, which the array has to agree with after every change.
+    #[derive(Debug, Default)]
+    struct Model {
+        width: usize,
+        rows: Vec<Vec<usize>>,
+    }
+
+    impl Model {
+        fn add_row(&mut self, data: Vec<usize>) -> bool {
+            if self.width == 0 {
+                self.width = data.len();
+            }
+            if data.len() != self.width {
+                return false;
+            }
+            if self.width > 0 {
+                self.rows.push(data);
+            }
+            true
+        }
+
+        fn remove_row(&mut self, row: usize) -> bool {
+            if row >= self.rows.len() {
+                return false;
+            }
+            self.rows.remove(row);
+            true
+        }
+
+        fn add_col(&mut self, data: Vec<usize>) -> bool {
+            if self.width == 0 {
+                self.rows = data.into_iter().map(|d| vec![d]).collect();
+                self.width = 1;
+                return true;
+            }
+            if data.len() != self.rows.len() {
+                return false;
+            }
+            self.rows.iter_mut().zip(data).for_each(|(r, d)| r.push(d));
+            self.width += 1;
+            true
+        }
+
+        fn remove_col(&mut self, col: usize) -> bool {
+            if col >= self.width {
+                return false;
+            }
+            self.rows.iter_mut().for_each(|r| {
+                r.remove(col);
+            });
+            self.width -= 1;
+            if self.width == 0 {
+                self.rows.clear();
+            }
+            true
+        }
+    }
+
+    // xorshift, so every run tries the same sequences without pulling in a crate.
+    struct Rng(u64);
+
+    impl Rng {
+        fn below(&mut self, n: usize) -> usize {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            (self.0 % n.max(1) as u64) as usize
+        }
+
+        // Usually the right length, sometimes one off either way.
+        fn length(&mut self, right: usize) -> usize {
+            match self.below(10) {
+                0 => right + 1,
+                1 => right.saturating_sub(1),
+                _ => right,
+            }
+        }
+    }
+
+    fn assert_same(a: &Faux2DArray<usize>, model: &Model) {
+        assert_eq!(a.width, model.width);
+        assert_eq!(a.height(), model.rows.len());
+        let rows: Vec<Vec<usize>> = a.rows().map(|r| r.into_iter().copied().collect()).collect();
+        assert_eq!(rows, model.rows);
+        for (y, row) in model.rows.iter().enumerate() {
+            assert_eq!(a.row(y).unwrap().copied().collect::<Vec<_>>(), *row);
+        }
+        assert!(a.row(model.rows.len()).is_none());
+        for x in 0..model.width {
+            let col: Vec<usize> = model.rows.iter().map(|r| r[x]).collect();
+            assert_eq!(a.col(x).unwrap().copied().collect::<Vec<_>>(), col);
+        }
+        assert!(a.col(model.width).is_none());
+    }
+
+    #[test]
+    fn test_mutations_against_model() {
+        let mut rng = Rng(0x2023_1225);
+        let mut next = 0;
+        for _ in 0..200 {
+            let width = rng.below(4);
+            let mut a: Faux2DArray<usize> = Faux2DArray::new(width);
+            let mut model = Model {
+                width,
+                ..Default::default()
+            };
+            for _ in 0..60 {
+                let (height, width) = (model.rows.len(), model.width);
+                let mut values = |n: usize| {
+                    next += n;
+                    (next - n..next).collect::<Vec<usize>>()
+                };
+                let (ok, expected) = match rng.below(4) {
+                    0 => {
+                        let data = values(rng.length(width));
+                        (a.add_row(data.clone()).is_ok(), model.add_row(data))
+                    }
+                    1 => {
+                        let row = rng.below(height + 2);
+                        (a.remove_row(row).is_ok(), model.remove_row(row))
+                    }
+                    2 => {
+                        let data = values(rng.length(height));
+                        (a.add_col(data.clone()).is_ok(), model.add_col(data))
+                    }
+                    _ => {
+                        let col = rng.below(width + 2);
+                        (a.remove_col(col).is_ok(), model.remove_col(col))
+                    }
+                };
+                assert_eq!(ok, expected);
+                assert_same(&a, &model);
+            }
+        }
+    }
+
     #[test]
     fn test_to_row_start() {
         let a = create_usize_array_2();
@@ -382,6 +701,56 @@ mod tests {
         assert_eq!(result.as_slice(), target);
     }
 
+    #[test]
+    fn test_from_text() {
+        let text = "#.S\n..#\n";
+        let grid: Faux2DArray<char> = text.parse().unwrap();
+        assert_eq!(grid.width, 3);
+        assert_eq!(grid.height(), 2);
+        assert_eq!(grid[(2, 0)], 'S');
+        assert_eq!(grid.to_string(), text);
+        let walls = Faux2DArray::from_text(text, |c| Some(c == '#')).unwrap();
+        assert_eq!(
+            walls.map(|w| if *w { '#' } else { '.' }).to_string(),
+            "#..\n..#\n"
+        );
+        let digits = Faux2DArray::from_text("12\n3x", |c| c.to_digit(10));
+        assert_eq!(
+            digits.unwrap_err(),
+            AocError::parse(2, 2, "invalid tile 'x'")
+        );
+        let uneven = "..\n.".parse::<Faux2DArray<char>>();
+        assert_eq!(
+            uneven.unwrap_err(),
+            AocError::parse(2, 1, "expected 2 tiles")
+        );
+        assert!("".parse::<Faux2DArray<char>>().is_err());
+    }
+
+    #[test]
+    fn test_get() {
+        let mut a = create_usize_array_2();
+        assert_eq!(a.get(1, 2), Some(&11));
+        assert_eq!(a.get(-1, 2), None);
+        assert_eq!(a.get(5, 0), None);
+        *a.get_mut(4, 4).unwrap() = 0;
+        a[(0, 0)] = 42;
+        assert_eq!(a.at(4, 4), Some(&0));
+        assert_eq!(a.positions().next(), Some(((0, 0), &42)));
+        assert_eq!(a.positions().nth(7), Some(((2, 1), &7)));
+    }
+
+    #[test]
+    fn test_neighbors() {
+        let a = create_usize_array_2();
+        let corner: Vec<_> = a.neighbors4(0, 0).collect();
+        assert_eq!(corner, vec![((1, 0), &1), ((0, 1), &5)]);
+        assert_eq!(a.neighbors8(0, 0).count(), 3);
+        assert_eq!(a.neighbors4(2, 2).count(), 4);
+        let around: Vec<usize> = a.neighbors8(2, 2).map(|(_, v)| *v).collect();
+        assert_eq!(around, vec![7, 13, 17, 11, 8, 18, 16, 6]);
+    }
+
     #[test]
     fn test_to_col_end() {
         let a = create_usize_array_2();
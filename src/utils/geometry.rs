@@ -1,21 +1,25 @@
+use crate::error::{parse_token, split_token, AocError, Result};
+
 #[derive(Debug)]
 pub struct Point {
     pub x: usize,
     pub y: usize,
 }
 
-#[derive(Debug, PartialEq, Eq)]
-pub struct ParsePointError;
+// Points also appear inside lines, so errors are reported against the whole line.
+fn parse_point(line: &str, token: &str) -> Result<Point> {
+    let (x, y) = split_token(line, token, ",")?;
+    Ok(Point {
+        x: parse_token(line, x, "coordinate")?,
+        y: parse_token(line, y, "coordinate")?,
+    })
+}
 
 impl std::str::FromStr for Point {
-    type Err = ParsePointError;
+    type Err = AocError;
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let pair: Vec<usize> = s.split(',').map(|x| x.parse::<usize>().unwrap()).collect();
-        Ok(Point {
-            x: pair[0],
-            y: pair[1],
-        })
+    fn from_str(s: &str) -> Result<Self> {
+        parse_point(s, s)
     }
 }
 
@@ -25,17 +29,14 @@ pub struct Line {
     pub b: Point,
 }
 
-#[derive(Debug, PartialEq, Eq)]
-pub struct ParseLineError;
-
 impl std::str::FromStr for Line {
-    type Err = ParseLineError;
+    type Err = AocError;
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut pair: Vec<Point> = s.split(" -> ").map(|x| x.parse().unwrap()).collect();
+    fn from_str(s: &str) -> Result<Self> {
+        let (a, b) = split_token(s, s, " -> ")?;
         Ok(Line {
-            a: pair.remove(0),
-            b: pair.remove(0),
+            a: parse_point(s, a)?,
+            b: parse_point(s, b)?,
         })
     }
 }
@@ -0,0 +1,185 @@
+use std::{
+    collections::BTreeMap,
+    fs,
+    process::{Command, Stdio},
+    thread,
+    time::{Duration, SystemTime},
+};
+
+use crate::solutions::Event;
+
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+// The answer and time of a part, or why it failed.
+pub type Outcome = std::result::Result<(String, Duration), String>;
+
+// What each part gave, as reported by `cargo solve <day> --json`.
+pub type RunResult = BTreeMap<u8, Outcome>;
+
+// Lines that are not about a part, like cargo's own output, are left out.
+pub fn parse_run_output(output: &str) -> RunResult {
+    let mut result = BTreeMap::new();
+    for line in output.lines() {
+        let Ok(report) = serde_json::from_str::<serde_json::Value>(line) else {
+            continue;
+        };
+        let Some(part) = report["part"].as_u64().and_then(|p| u8::try_from(p).ok()) else {
+            continue;
+        };
+        let answer = report["answer"].as_str();
+        let elapsed = report["duration_ns"].as_u64().map(Duration::from_nanos);
+        let outcome = match (answer, elapsed, report["error"].as_str()) {
+            (Some(answer), Some(elapsed), _) => Ok((answer.to_string(), elapsed)),
+            (_, _, Some(error)) => Err(error.to_string()),
+            _ => continue,
+        };
+        result.insert(part, outcome);
+    }
+    result
+}
+
+pub fn format_change(
+    day: u8,
+    part: u8,
+    current: &(String, Duration),
+    previous: Option<&(String, Duration)>,
+) -> String {
+    let (answer, elapsed) = current;
+    match previous {
+        None => format!(
+            "🎄 Day {:02} / Part {:02}: {} ({:.2?})",
+            day, part, answer, elapsed
+        ),
+        Some((last_answer, last_elapsed)) => {
+            let answer = if last_answer == answer {
+                answer.clone()
+            } else {
+                format!("{} → {}", last_answer, answer)
+            };
+            let change = (elapsed.as_secs_f64() / last_elapsed.as_secs_f64().max(f64::EPSILON)
+                - 1.0)
+                * 100.0;
+            format!(
+                "🎄 Day {:02} / Part {:02}: {} ({:.2?} → {:.2?}, {:+.1}%)",
+                day, part, answer, last_elapsed, elapsed, change
+            )
+        }
+    }
+}
+
+// The day's module and every input file of the day, example ones included.
+fn watched_files(event: &Event, root: &str, day: u8) -> Vec<String> {
+    let mut files = vec![format!("{}/day{:02}.rs", event.solutions_dir(root), day)];
+    let prefix = format!("day{:02}", day);
+    let inputs_dir = event.inputs_dir(root);
+    if let Ok(entries) = fs::read_dir(&inputs_dir) {
+        for entry in entries.flatten() {
+            let name = entry.file_name().to_string_lossy().to_string();
+            if name.starts_with(&prefix) && name.ends_with(".txt") {
+                files.push(format!("{}/{}", inputs_dir, name));
+            }
+        }
+    }
+    files.sort();
+    files
+}
+
+fn snapshot(event: &Event, root: &str, day: u8) -> Vec<(String, Option<SystemTime>)> {
+    watched_files(event, root, day)
+        .into_iter()
+        .map(|f| {
+            let modified = fs::metadata(&f).and_then(|m| m.modified()).ok();
+            (f, modified)
+        })
+        .collect()
+}
+
+// The solution has to be rebuilt when its source changes, so every run goes through cargo. A part
+// failing makes it exit with an error, but the other parts are still reported.
+fn run(event: &Event, root: &str, day: u8) -> Option<RunResult> {
+    let output = Command::new("cargo")
+        .args(["run", "--release", "--quiet", "--bin", "aoc_2023", "--"])
+        .args(["--event", event.name, "--root", root])
+        .arg(day.to_string())
+        .arg("--json")
+        .stderr(Stdio::inherit())
+        .output()
+        .ok()?;
+    let result = parse_run_output(&String::from_utf8_lossy(&output.stdout));
+    if result.is_empty() && !output.status.success() {
+        return None;
+    }
+    Some(result)
+}
+
+pub fn watch(event: &Event, root: &str, day: u8) -> ! {
+    let mut last_snapshot = snapshot(event, root, day);
+    // The last answer and time of each part that was solved, failures keep the one before.
+    let mut last_solved: BTreeMap<u8, (String, Duration)> = BTreeMap::new();
+    println!(
+        "👀 Watching {} files of day {:02}, press Ctrl-C to stop.",
+        last_snapshot.len(),
+        day
+    );
+    loop {
+        println!("---");
+        match run(event, root, day) {
+            Some(result) => {
+                for (part, outcome) in result {
+                    match outcome {
+                        Ok(current) => {
+                            let previous = last_solved.get(&part);
+                            println!("{}", format_change(day, part, &current, previous));
+                            last_solved.insert(part, current);
+                        }
+                        Err(e) => println!("🎄 Day {:02} / Part {:02} failed: {}", day, part, e),
+                    }
+                }
+            }
+            None => println!("🎄 Day {:02} failed, waiting for changes.", day),
+        }
+        loop {
+            thread::sleep(POLL_INTERVAL);
+            let current = snapshot(event, root, day);
+            if current != last_snapshot {
+                last_snapshot = current;
+                break;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_run_output() {
+        let output = concat!(
+            r#"{"answer":"240320250","day":5,"duration_ns":5810,"error":null,"input":"src/inputs/day05.txt","part":1}"#,
+            "\n",
+            r#"{"answer":null,"day":5,"duration_ns":1200,"error":"Day 05: seeds do not come in pairs","input":"src/inputs/day05.txt","part":2}"#,
+            "\n",
+        );
+        let result = parse_run_output(output);
+        assert_eq!(
+            result[&1],
+            Ok((String::from("240320250"), Duration::from_nanos(5810)))
+        );
+        assert_eq!(
+            result[&2],
+            Err(String::from("Day 05: seeds do not come in pairs"))
+        );
+        assert!(parse_run_output("error: could not compile `aoc_2023`").is_empty());
+    }
+
+    #[test]
+    fn test_format_change() {
+        let previous = (String::from("35"), Duration::from_millis(2));
+        let current = (String::from("46"), Duration::from_millis(3));
+        assert_eq!(
+            format_change(5, 2, &current, Some(&previous)),
+            "🎄 Day 05 / Part 02: 35 → 46 (2.00ms → 3.00ms, +50.0%)"
+        );
+    }
+}